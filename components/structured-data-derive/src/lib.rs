@@ -0,0 +1,213 @@
+// This file is part of syslog. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog/master/COPYRIGHT. No part of syslog, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog/master/COPYRIGHT.
+
+#![allow(non_snake_case)]
+#![allow(non_upper_case_globals)]
+// This crate, and the validation it shares with syslog, are written in the same (Rust 2015) style as syslog, eg using `try!` and `...` patterns
+#![allow(deprecated)]
+#![allow(ellipsis_inclusive_range_patterns)]
+#![allow(clippy::redundant_field_names, clippy::redundant_static_lifetimes, clippy::tabs_in_doc_comments, clippy::manual_contains, clippy::manual_range_contains)]
+
+//! `#[derive(StructuredDataElement)]`, which implements `syslog::rfc5424::ToStructuredDataElement`
+//! SD-IDs and PARAM-NAMEs are validated at compile time using the same rules as `syslog::rfc5424::StructuredDataId` at runtime
+//!
+//! ```ignore
+//! #[derive(StructuredDataElement)]
+//! #[sd(id = "http@38188")]
+//! struct Http
+//! {
+//! 	method: String,
+//! 	#[sd(rename = "status")] statusCode: u16,
+//! 	#[sd(skip)] body: Vec<u8>,
+//! 	userAgent: Option<String>,
+//! }
+//! ```
+//! Fields must implement `Display`; `Option` fields are omitted when `None`
+
+
+extern crate proc_macro;
+extern crate proc_macro2;
+#[macro_use] extern crate quote;
+#[macro_use] extern crate syn;
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use syn::Data;
+use syn::DeriveInput;
+use syn::Error;
+use syn::Field;
+use syn::Fields;
+use syn::LitStr;
+use syn::Type;
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+
+#[allow(dead_code)] #[path = "../../../src/rfc5424/structuredDataNameError.rs"] mod structuredDataNameError;
+#[allow(dead_code)] #[path = "../../../src/rfc5424/structuredDataNameValidation.rs"] mod structuredDataNameValidation;
+use structuredDataNameValidation::validate_structured_data_id;
+use structuredDataNameValidation::validate_structured_data_parameter_name;
+
+
+#[proc_macro_derive(StructuredDataElement, attributes(sd))]
+pub fn derive_structured_data_element(input: TokenStream) -> TokenStream
+{
+	let input = parse_macro_input!(input as DeriveInput);
+	match expand(&input)
+	{
+		Ok(expanded) => expanded.into(),
+		Err(error) => error.to_compile_error().into(),
+	}
+}
+
+fn expand(input: &DeriveInput) -> Result<TokenStream2, Error>
+{
+	let id = try!(structuredDataId(input));
+	
+	let fields = match input.data
+	{
+		Data::Struct(ref data) => match data.fields
+		{
+			Fields::Named(ref fields) => &fields.named,
+			_ => return Err(Error::new(input.ident.span(), "#[derive(StructuredDataElement)] requires a struct with named fields")),
+		},
+		_ => return Err(Error::new(input.ident.span(), "#[derive(StructuredDataElement)] requires a struct with named fields")),
+	};
+	
+	let mut parameters = Vec::with_capacity(fields.len());
+	for field in fields
+	{
+		if let Some(parameter) = try!(parameter(field))
+		{
+			parameters.push(parameter);
+		}
+	}
+	
+	let name = &input.ident;
+	let (implGenerics, typeGenerics, whereClause) = input.generics.split_for_impl();
+	Ok(quote!
+	{
+		impl #implGenerics ::syslog::rfc5424::ToStructuredDataElement for #name #typeGenerics #whereClause
+		{
+			fn to_structured_data_element(&self) -> ::syslog::rfc5424::OwnedStructuredDataElement
+			{
+				let mut element = ::syslog::rfc5424::OwnedStructuredDataElement::new(#id);
+				#(#parameters)*
+				element
+			}
+		}
+	})
+}
+
+fn structuredDataId(input: &DeriveInput) -> Result<String, Error>
+{
+	let mut id: Option<LitStr> = None;
+	for attribute in input.attrs.iter().filter(|attribute| attribute.path().is_ident("sd"))
+	{
+		try!(attribute.parse_nested_meta(|meta|
+		{
+			if meta.path.is_ident("id")
+			{
+				id = Some(try!(try!(meta.value()).parse()));
+				Ok(())
+			}
+			else
+			{
+				Err(meta.error("expected #[sd(id = \"name@enterpriseNumber\")]"))
+			}
+		}));
+	}
+	
+	let id = try!(id.ok_or_else(|| Error::new(input.ident.span(), "#[derive(StructuredDataElement)] requires #[sd(id = \"name@enterpriseNumber\")]")));
+	match validate_structured_data_id(&id.value())
+	{
+		Ok(()) => Ok(id.value()),
+		Err(error) => Err(Error::new(id.span(), format!("invalid SD-ID: {}", error))),
+	}
+}
+
+fn parameter(field: &Field) -> Result<Option<TokenStream2>, Error>
+{
+	let ident = field.ident.as_ref().unwrap();
+	
+	let mut skip = false;
+	let mut rename: Option<LitStr> = None;
+	for attribute in field.attrs.iter().filter(|attribute| attribute.path().is_ident("sd"))
+	{
+		try!(attribute.parse_nested_meta(|meta|
+		{
+			if meta.path.is_ident("skip")
+			{
+				skip = true;
+				Ok(())
+			}
+			else if meta.path.is_ident("rename")
+			{
+				rename = Some(try!(try!(meta.value()).parse()));
+				Ok(())
+			}
+			else
+			{
+				Err(meta.error("expected #[sd(skip)] or #[sd(rename = \"name\")]"))
+			}
+		}));
+	}
+	
+	if skip
+	{
+		return Ok(None);
+	}
+	
+	let (name, span) = match rename
+	{
+		Some(rename) => (rename.value(), rename.span()),
+		None => (ident.unraw().to_string(), ident.span()),
+	};
+	if let Err(error) = validate_structured_data_parameter_name(&name)
+	{
+		return Err(Error::new(span, format!("invalid PARAM-NAME: {}", error)));
+	}
+	
+	Ok(Some(if isOption(&field.ty)
+	{
+		quote_spanned!
+		{
+			field.span() =>
+			if let Some(ref value) = self.#ident
+			{
+				element = element.param(#name, value);
+			}
+		}
+	}
+	else
+	{
+		quote_spanned!
+		{
+			field.span() =>
+			element = element.param(#name, &self.#ident);
+		}
+	}))
+}
+
+/// Only recognises `Option<T>`, `std::option::Option<T>` and similar paths, not type aliases
+fn isOption(ty: &Type) -> bool
+{
+	match *ty
+	{
+		Type::Path(ref typePath) => typePath.qself.is_none() && typePath.path.segments.last().map(|segment| segment.ident == "Option").unwrap_or(false),
+		_ => false,
+	}
+}
+
+#[test]
+fn rejectsInvalidNamesAtCompileTime()
+{
+	let valid: DeriveInput = syn::parse_str("#[sd(id = \"http@38188\")] struct Http { method: String, #[sd(skip)] body: Vec<u8>, userAgent: Option<String>, r#type: String }").unwrap();
+	let expanded = expand(&valid).unwrap().to_string();
+	assert!(expanded.contains("\"type\""));
+	assert!(!expanded.contains("\"r#type\""));
+	
+	let unregistered: DeriveInput = syn::parse_str("#[sd(id = \"http\")] struct Http { method: String }").unwrap();
+	assert!(expand(&unregistered).is_err());
+	
+	let invalidParameterName: DeriveInput = syn::parse_str("#[sd(id = \"http@38188\")] struct Http { #[sd(rename = \"a=b\")] method: String }").unwrap();
+	assert!(expand(&invalidParameterName).is_err());
+}
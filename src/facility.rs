@@ -2,9 +2,9 @@
 // Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
 
 extern crate libc;
-use Priority;
+use LocalPriority;
 use Severity;
-use super::syslog2Senders::Rfc3164Facility;
+use syslogSenders::Rfc3164Facility;
 use self::libc::c_int;
 
 /// Fill in for lack of this value in Android bionic's libc
@@ -17,7 +17,7 @@ use self::libc::c_int;
 /// `LOG_NETINFO`, `LOG_REMOTEAUTH`, `LOG_INSTALL`, `LOG_RAS` and `LOG_LAUNCHD` are only available on Mac OS X for local logging
 /// `LOG_CRON` differs in value on Solaris, and _should not_ be used to send syslog2 messages on the wire
 #[allow(non_camel_case_types)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(i32)] // We'd like to use c_int here, but the compiler won't let us
 #[cfg(not(target_os = "windows"))]
 pub enum Facility
@@ -58,7 +58,7 @@ pub enum Facility
 /// These values are 'fakes' to allow some measure of syslog2 compatibility on Windows
 /// Values match those used on Linux
 #[allow(non_camel_case_types)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(i32)] // We'd like to use c_int here, but the compiler won't let us
 #[cfg(target_os = "windows")]
 pub enum Facility
@@ -88,11 +88,49 @@ pub enum Facility
 impl Facility
 {
 	#[inline(always)]
-	pub fn toPriority(self, severity: Severity) -> Priority
+	pub fn toPriority(self, severity: Severity) -> LocalPriority
 	{
 		severity.toPriority(self)
 	}
 	
+	/// `local` must be pre-shifted, ie already masked with LOG_FACMASK
+	/// Returns None if `local` is not one of this platform's libc facility values
+	pub fn fromLocal(local: c_int) -> Option<Facility>
+	{
+		const All: &'static [Facility] = &[
+			Facility::LOG_KERN,
+			Facility::LOG_USER,
+			Facility::LOG_MAIL,
+			Facility::LOG_DAEMON,
+			Facility::LOG_AUTH,
+			Facility::LOG_SYSLOG,
+			Facility::LOG_LPR,
+			Facility::LOG_NEWS,
+			Facility::LOG_UUCP,
+			Facility::LOG_CRON,
+			#[cfg(not(target_os = "solaris"))] Facility::LOG_AUTHPRIV,
+			#[cfg(not(target_os = "solaris"))] Facility::LOG_FTP,
+			#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))] Facility::LOG_NTP,
+			#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))] Facility::LOG_SECURITY,
+			#[cfg(any(target_os = "freebsd", target_os = "dragonfly"))] Facility::LOG_CONSOLE,
+			#[cfg(target_os = "macos")] Facility::LOG_NETINFO,
+			#[cfg(target_os = "macos")] Facility::LOG_REMOTEAUTH,
+			#[cfg(target_os = "macos")] Facility::LOG_INSTALL,
+			#[cfg(target_os = "macos")] Facility::LOG_RAS,
+			Facility::LOG_LOCAL0,
+			Facility::LOG_LOCAL1,
+			Facility::LOG_LOCAL2,
+			Facility::LOG_LOCAL3,
+			Facility::LOG_LOCAL4,
+			Facility::LOG_LOCAL5,
+			Facility::LOG_LOCAL6,
+			Facility::LOG_LOCAL7,
+			#[cfg(target_os = "macos")] Facility::LOG_LAUNCHD,
+		];
+		
+		All.iter().find(|facility| (**facility as c_int) == local).map(|facility| *facility)
+	}
+	
 	/// Returns `LOG_AUTHPRIV` except on Solaris, where it returns `LOG_AUTH`
 	#[inline(always)]
 	#[cfg(not(target_os = "solaris"))]
@@ -129,7 +167,7 @@ impl Facility
 			Facility::LOG_SYSLOG => Rfc3164Facility::syslog2,
 			Facility::LOG_LPR => Rfc3164Facility::lpr,
 			Facility::LOG_NEWS => Rfc3164Facility::news,
-			Facility::LOG_UUCP => Rfc3164Facility::news,
+			Facility::LOG_UUCP => Rfc3164Facility::uucp,
 			Facility::LOG_CRON => Rfc3164Facility::clock,
 			#[cfg(not(target_os = "solaris"))] Facility::LOG_AUTHPRIV => Rfc3164Facility::authpriv,
 			#[cfg(not(target_os = "solaris"))] Facility::LOG_FTP => Rfc3164Facility::ftp,
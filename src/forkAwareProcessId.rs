@@ -0,0 +1,82 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::io::Write;
+use std::process;
+use rfc5424::TruncatedUsAsciiPrintableString;
+use rfc5424::truncatedUsAsciiPrintableString::WriteTruncatedUsAsciiPrintableString;
+use fork_generation;
+
+
+/// A PROCID that is correct in a child process after `fork()`
+/// Before a fork, the value given at construction is used; afterwards, the child's own process id is
+#[derive(Debug)]
+pub struct ForkAwareProcessId
+{
+	processId: TruncatedUsAsciiPrintableString,
+	forkGeneration: usize,
+}
+
+impl ForkAwareProcessId
+{
+	pub fn new(processId: &str) -> ForkAwareProcessId
+	{
+		ForkAwareProcessId
+		{
+			processId: TruncatedUsAsciiPrintableString::new(processId, 128),
+			forkGeneration: fork_generation(),
+		}
+	}
+	
+	pub fn write(&self, writer: &mut Vec<u8>)
+	{
+		if fork_generation() == self.forkGeneration
+		{
+			writer.write_truncated(&self.processId);
+		}
+		else
+		{
+			write!(writer, "{}", process::id()).unwrap();
+		}
+	}
+}
+
+#[cfg(unix)]
+#[test]
+fn writesTheChildsProcessIdAfterFork()
+{
+	extern crate libc;
+	
+	let processId = ForkAwareProcessId::new("parent");
+	
+	// Allocated before forking, so the child does not need to call malloc()
+	let mut written = Vec::with_capacity(128);
+	let mut expected = Vec::with_capacity(128);
+	processId.write(&mut written);
+	assert_eq!(&written[..], b"parent");
+	written.clear();
+	
+	match unsafe { libc::fork() }
+	{
+		-1 => panic!("fork() failed"),
+		
+		0 =>
+		{
+			processId.write(&mut written);
+			write!(expected, "{}", process::id()).unwrap();
+			let exitCode = if written == expected { 0 } else { 1 };
+			unsafe { libc::_exit(exitCode) }
+		},
+		
+		child =>
+		{
+			let mut status = 0;
+			assert_eq!(unsafe { libc::waitpid(child, &mut status, 0) }, child);
+			assert!(libc::WIFEXITED(status), "child did not exit normally");
+			assert_eq!(libc::WEXITSTATUS(status), 0, "PROCID in the child was not the child's process id");
+			
+			processId.write(&mut written);
+			assert_eq!(&written[..], b"parent");
+		},
+	}
+}
@@ -0,0 +1,35 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate libc;
+use std::sync::Once;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+
+
+static ForkGeneration: AtomicUsize = AtomicUsize::new(0);
+
+static RegisterChildHandler: Once = Once::new();
+
+/// Runs in the child after `fork()`; must be async-signal-safe, so only increments an atomic
+#[cfg(unix)]
+unsafe extern "C" fn incrementForkGenerationInChild()
+{
+	ForkGeneration.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Incremented in the child process after every `fork()`, using `pthread_atfork()`
+/// Compare against a previously obtained value to detect that per-process state (PROCID, sequence numbers, sockets) was inherited from a parent
+/// The first call registers the `pthread_atfork()` handler; `SyslogRfcConstructor::new()` calls this, so it is normally registered before any fork
+/// Always zero on Windows
+pub fn fork_generation() -> usize
+{
+	#[cfg(unix)]
+	RegisterChildHandler.call_once(||
+	{
+		let result = unsafe { self::libc::pthread_atfork(None, None, Some(incrementForkGenerationInChild)) };
+		debug_assert!(result == 0, "pthread_atfork() failed");
+	});
+	
+	ForkGeneration.load(Ordering::SeqCst)
+}
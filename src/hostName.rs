@@ -0,0 +1,189 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+use std::thread;
+use rfc5424::TruncatedUsAsciiPrintableString;
+use rfc5424::truncatedUsAsciiPrintableString::WriteTruncatedUsAsciiPrintableString;
+use HostNameStrategy;
+
+
+const NILVALUE: &'static str = "-";
+
+const MaximumHostNameLength: usize = 255;
+
+static InvalidationGeneration: AtomicUsize = AtomicUsize::new(0);
+
+/// Causes every `HostName` to be resolved again before it is next written; for example, call this from a SIGHUP handler or when notified that the system host name has changed
+/// Async-signal-safe
+pub fn invalidate_host_names()
+{
+	InvalidationGeneration.fetch_add(1, Ordering::SeqCst);
+}
+
+#[derive(Debug)]
+struct ResolvedHostName
+{
+	hostName: TruncatedUsAsciiPrintableString,
+	resolvedAt: Instant,
+	invalidationGeneration: usize,
+}
+
+/// A HOSTNAME header field, resolved using a `HostNameStrategy`
+/// It is resolved again after `refreshInterval` has elapsed and after `invalidate_host_names()`, so long-running daemons do not report stale names
+/// This happens on a background thread, so `write()` never waits for `getaddrinfo()`; the previously resolved name is written until it completes
+#[derive(Debug)]
+pub struct HostName
+{
+	strategy: HostNameStrategy,
+	refreshInterval: Option<Duration>,
+	resolved: Arc<RwLock<ResolvedHostName>>,
+	refreshing: Arc<AtomicBool>,
+}
+
+impl HostName
+{
+	/// If `refreshInterval` is None, the host name is only resolved again on demand
+	pub fn new(strategy: HostNameStrategy, refreshInterval: Option<Duration>) -> HostName
+	{
+		let resolved = HostName::resolve(&strategy);
+		HostName
+		{
+			strategy: strategy,
+			refreshInterval: refreshInterval,
+			resolved: Arc::new(RwLock::new(resolved)),
+			refreshing: Arc::new(AtomicBool::new(false)),
+		}
+	}
+	
+	/// Never resolved again; an empty `hostName` is sent as NILVALUE
+	#[inline(always)]
+	pub fn fixed(hostName: &str) -> HostName
+	{
+		HostName::new(HostNameStrategy::Override(hostName.to_owned()), None)
+	}
+	
+	#[inline(always)]
+	pub fn strategy(&self) -> &HostNameStrategy
+	{
+		&self.strategy
+	}
+	
+	/// Resolves immediately, on this thread
+	pub fn refresh(&self)
+	{
+		let resolved = HostName::resolve(&self.strategy);
+		*self.resolved.write().unwrap() = resolved;
+	}
+	
+	pub fn write(&self, writer: &mut Vec<u8>)
+	{
+		if self.isStale()
+		{
+			self.refreshInBackground();
+		}
+		
+		writer.write_truncated(&self.resolved.read().unwrap().hostName);
+	}
+	
+	/// At most one refresh is in progress at a time
+	fn refreshInBackground(&self)
+	{
+		if self.refreshing.swap(true, Ordering::AcqRel)
+		{
+			return;
+		}
+		
+		let strategy = self.strategy.clone();
+		let resolved = self.resolved.clone();
+		let refreshing = self.refreshing.clone();
+		let spawned = thread::Builder::new().name("syslog2-hostname".to_owned()).spawn(move ||
+		{
+			let newlyResolved = HostName::resolve(&strategy);
+			*resolved.write().unwrap() = newlyResolved;
+			refreshing.store(false, Ordering::Release);
+		});
+		
+		// Tried again on the next write
+		if spawned.is_err()
+		{
+			self.refreshing.store(false, Ordering::Release);
+		}
+	}
+	
+	fn isStale(&self) -> bool
+	{
+		if self.strategy.isFixed()
+		{
+			return false;
+		}
+		
+		let resolved = self.resolved.read().unwrap();
+		if resolved.invalidationGeneration != InvalidationGeneration.load(Ordering::SeqCst)
+		{
+			return true;
+		}
+		
+		match self.refreshInterval
+		{
+			None => false,
+			Some(refreshInterval) => resolved.resolvedAt.elapsed() >= refreshInterval,
+		}
+	}
+	
+	fn resolve(strategy: &HostNameStrategy) -> ResolvedHostName
+	{
+		// Read before resolving, so an invalidation during resolution is not lost
+		let invalidationGeneration = InvalidationGeneration.load(Ordering::SeqCst);
+		let hostName = match strategy.resolve()
+		{
+			None => TruncatedUsAsciiPrintableString::new(NILVALUE, MaximumHostNameLength),
+			Some(hostName) => TruncatedUsAsciiPrintableString::new(&hostName, MaximumHostNameLength),
+		};
+		
+		ResolvedHostName
+		{
+			hostName: hostName,
+			resolvedAt: Instant::now(),
+			invalidationGeneration: invalidationGeneration,
+		}
+	}
+}
+
+#[test]
+fn fixedHostNamesAreNeverRefreshedAndEmptyIsNilValue()
+{
+	let hostName = HostName::fixed("");
+	invalidate_host_names();
+	assert!(!hostName.isStale());
+	
+	let mut writer = Vec::new();
+	hostName.write(&mut writer);
+	assert_eq!(&writer[..], b"-");
+}
+
+#[test]
+fn staleHostNamesAreWrittenWhilstRefreshingInTheBackground()
+{
+	let hostName = HostName::new(HostNameStrategy::ShortName, Some(Duration::from_secs(0)));
+	let resolved = hostName.resolved.read().unwrap().hostName.clone();
+	assert!(hostName.isStale());
+	
+	let mut writer = Vec::new();
+	hostName.write(&mut writer);
+	let mut expected = Vec::new();
+	expected.write_truncated(&resolved);
+	assert_eq!(writer, expected);
+	
+	while hostName.refreshing.load(Ordering::Acquire)
+	{
+		thread::yield_now();
+	}
+	assert_eq!(hostName.resolved.read().unwrap().hostName, resolved);
+}
@@ -0,0 +1,240 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate libc;
+use std::net::IpAddr;
+
+
+/// How to choose the HOSTNAME header field; see RFC 5424 section 6.2.4
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HostNameStrategy
+{
+	/// The canonical name of the system host name, from `getaddrinfo()` with `AI_CANONNAME`; falls back to the system host name if that contains a `.`
+	FullyQualifiedDomainName,
+	
+	/// The system host name up to, but excluding, the first `.`
+	ShortName,
+	
+	StaticIpAddress(IpAddr),
+	
+	/// Used verbatim; an empty string is sent as NILVALUE
+	Override(String),
+	
+	/// RFC 5424's order of preference: FQDN, then static IP address (if given), then the system host name
+	/// Dynamic IP addresses are not discovered, as the RFC considers them least preferable, so the final fallback is NILVALUE
+	RfcPreferenceOrder
+	{
+		staticIpAddress: Option<IpAddr>,
+	},
+}
+
+impl Default for HostNameStrategy
+{
+	#[inline(always)]
+	fn default() -> HostNameStrategy
+	{
+		HostNameStrategy::RfcPreferenceOrder
+		{
+			staticIpAddress: None,
+		}
+	}
+}
+
+impl HostNameStrategy
+{
+	/// None if nothing could be resolved, in which case NILVALUE should be sent
+	pub fn resolve(&self) -> Option<String>
+	{
+		match *self
+		{
+			HostNameStrategy::FullyQualifiedDomainName => HostNameStrategy::fullyQualifiedDomainName(),
+			
+			HostNameStrategy::ShortName => systemHostName().map(shortName),
+			
+			HostNameStrategy::StaticIpAddress(ref ipAddress) => Some(ipAddress.to_string()),
+			
+			HostNameStrategy::Override(ref hostName) => if hostName.is_empty()
+			{
+				None
+			}
+			else
+			{
+				Some(hostName.clone())
+			},
+			
+			HostNameStrategy::RfcPreferenceOrder { ref staticIpAddress } => inRfcPreferenceOrder(HostNameStrategy::fullyQualifiedDomainName, staticIpAddress.as_ref(), systemHostName),
+		}
+	}
+	
+	/// True if resolving does not consult the system, so always gives the same result
+	#[inline(always)]
+	pub fn isFixed(&self) -> bool
+	{
+		match *self
+		{
+			HostNameStrategy::StaticIpAddress(_) | HostNameStrategy::Override(_) => true,
+			_ => false,
+		}
+	}
+	
+	fn fullyQualifiedDomainName() -> Option<String>
+	{
+		systemHostName().and_then(|hostName|
+		{
+			let canonicalName = canonicalName(&hostName);
+			fullyQualifiedDomainName(hostName, canonicalName)
+		})
+	}
+}
+
+fn shortName(hostName: String) -> String
+{
+	match hostName.find('.')
+	{
+		None => hostName,
+		Some(index) => hostName[.. index].to_owned(),
+	}
+}
+
+fn fullyQualifiedDomainName(hostName: String, canonicalName: Option<String>) -> Option<String>
+{
+	match canonicalName
+	{
+		Some(canonicalName) if canonicalName.contains('.') => Some(canonicalName),
+		_ => if hostName.contains('.')
+		{
+			Some(hostName)
+		}
+		else
+		{
+			None
+		},
+	}
+}
+
+/// Each fallback is only looked up if the previous one gave nothing
+fn inRfcPreferenceOrder<F: FnOnce() -> Option<String>, H: FnOnce() -> Option<String>>(fullyQualifiedDomainName: F, staticIpAddress: Option<&IpAddr>, systemHostName: H) -> Option<String>
+{
+	fullyQualifiedDomainName().or_else(|| staticIpAddress.map(|ipAddress| ipAddress.to_string())).or_else(systemHostName)
+}
+
+#[cfg(unix)]
+fn systemHostName() -> Option<String>
+{
+	use std::ffi::CStr;
+	use self::libc::c_char;
+	
+	// Larger than HOST_NAME_MAX (255) on all supported platforms, with room for a terminating NUL
+	let mut buffer: [c_char; 257] = [0; 257];
+	if unsafe { self::libc::gethostname(buffer.as_mut_ptr(), buffer.len() - 1) } != 0
+	{
+		return None;
+	}
+	
+	let hostName = unsafe { CStr::from_ptr(buffer.as_ptr()) };
+	match hostName.to_str()
+	{
+		Ok("") | Err(_) => None,
+		Ok(hostName) => Some(hostName.to_owned()),
+	}
+}
+
+#[cfg(not(unix))]
+fn systemHostName() -> Option<String>
+{
+	use std::env;
+	
+	env::var("COMPUTERNAME").ok()
+}
+
+#[cfg(unix)]
+fn canonicalName(hostName: &str) -> Option<String>
+{
+	use std::ffi::CStr;
+	use std::ffi::CString;
+	use std::mem::zeroed;
+	use std::ptr::null;
+	use std::ptr::null_mut;
+	use self::libc::addrinfo;
+	use self::libc::getaddrinfo;
+	use self::libc::freeaddrinfo;
+	use self::libc::AF_UNSPEC;
+	use self::libc::AI_CANONNAME;
+	use self::libc::SOCK_DGRAM;
+	
+	let node = match CString::new(hostName)
+	{
+		Err(_) => return None,
+		Ok(node) => node,
+	};
+	
+	let mut hints: addrinfo = unsafe { zeroed() };
+	hints.ai_flags = AI_CANONNAME;
+	hints.ai_family = AF_UNSPEC;
+	hints.ai_socktype = SOCK_DGRAM;
+	
+	let mut results: *mut addrinfo = null_mut();
+	if unsafe { getaddrinfo(node.as_ptr(), null(), &hints, &mut results) } != 0
+	{
+		return None;
+	}
+	
+	// Only the first result has ai_canonname set
+	let canonicalName = unsafe
+	{
+		if results.is_null() || (*results).ai_canonname.is_null()
+		{
+			None
+		}
+		else
+		{
+			CStr::from_ptr((*results).ai_canonname).to_str().ok().map(|canonicalName| canonicalName.to_owned())
+		}
+	};
+	
+	unsafe { freeaddrinfo(results) };
+	canonicalName
+}
+
+#[cfg(not(unix))]
+fn canonicalName(_hostName: &str) -> Option<String>
+{
+	None
+}
+
+#[test]
+fn shortNameExcludesTheDomain()
+{
+	assert_eq!(shortName("macpro.example.com".to_owned()), "macpro");
+	assert_eq!(shortName("macpro".to_owned()), "macpro");
+	
+	if let Some(resolved) = HostNameStrategy::ShortName.resolve()
+	{
+		assert!(!resolved.contains('.'));
+		assert!(systemHostName().unwrap().starts_with(&resolved));
+	}
+}
+
+#[test]
+fn emptyOverrideIsNilValue()
+{
+	assert_eq!(HostNameStrategy::Override("".to_owned()).resolve(), None);
+	assert_eq!(HostNameStrategy::Override("macpro.example.com".to_owned()).resolve(), Some("macpro.example.com".to_owned()));
+}
+
+#[test]
+fn rfcPreferenceOrderFallsBackInTurn()
+{
+	use std::net::Ipv4Addr;
+	
+	assert_eq!(fullyQualifiedDomainName("macpro".to_owned(), Some("macpro.example.com".to_owned())), Some("macpro.example.com".to_owned()));
+	assert_eq!(fullyQualifiedDomainName("macpro.example.com".to_owned(), Some("macpro".to_owned())), Some("macpro.example.com".to_owned()));
+	assert_eq!(fullyQualifiedDomainName("macpro".to_owned(), None), None);
+	
+	let staticIpAddress = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 1));
+	let unreachable = || -> Option<String> { panic!("should not have been looked up") };
+	assert_eq!(inRfcPreferenceOrder(|| Some("macpro.example.com".to_owned()), Some(&staticIpAddress), unreachable), Some("macpro.example.com".to_owned()));
+	assert_eq!(inRfcPreferenceOrder(|| None, Some(&staticIpAddress), unreachable), Some("192.0.2.1".to_owned()));
+	assert_eq!(inRfcPreferenceOrder(|| None, None, || Some("macpro".to_owned())), Some("macpro".to_owned()));
+	assert_eq!(inRfcPreferenceOrder(|| None, None, || None), None);
+}
@@ -9,21 +9,36 @@
 #[macro_use] extern crate once;
 extern crate libc;
 use std::ffi::CStr;
+use std::ffi::CString;
 
 mod severity;
 pub use severity::Severity;
 
+mod severityParseError;
+pub use severityParseError::SeverityParseError;
+
 mod facility;
 pub use facility::Facility;
 
 mod priority;
 pub use priority::Priority;
-pub use priority::PriorityTrait;
+
+mod priorityParseError;
+pub use priorityParseError::PriorityParseError;
+
+mod localPriority;
+pub use localPriority::LocalPriority;
 
 mod logMask;
 pub use logMask::LogMask;
-pub use logMask::LogMaskTrait;
 pub use logMask::default_log_mask;
+pub use logMask::active_log_mask;
+
+mod selector;
+pub use selector::Selector;
+
+mod selectorParseError;
+pub use selectorParseError::SelectorParseError;
 
 mod logOptions;
 pub use logOptions::LogOptions;
@@ -37,12 +52,38 @@ mod syslogRfcConstructor;
 pub use syslogRfc::SyslogRfc;
 mod syslogRfc;
 
+pub use forkGeneration::fork_generation;
+mod forkGeneration;
+
+pub use forkAwareProcessId::ForkAwareProcessId;
+mod forkAwareProcessId;
+
+pub use hostNameStrategy::HostNameStrategy;
+mod hostNameStrategy;
+
+pub use hostName::HostName;
+pub use hostName::invalidate_host_names;
+mod hostName;
+
+pub use messageHeaderOverrides::MessageHeaderOverrides;
+mod messageHeaderOverrides;
+
+pub use panicHook::install_panic_hook;
+mod panicHook;
+
 pub mod syslogSenders;
 
 pub mod rfc5424;
 
+#[cfg(feature = "log")] pub mod logFacade;
+
+#[cfg(feature = "tracing")] pub mod tracingLayer;
+
+#[cfg(feature = "slog")] pub mod slogDrain;
+
+#[cfg(feature = "serde")] pub mod serdeStructuredData;
+
 // TODO: What are the Windows event log equivalents?
-// TODO: Integrate with the log crate...
 // TODO: Hand-off thread for SyslogSender, because they block
 // TODO: TCP reconnect on failure. Not great, as possible we will have sent a partial message...
 // TODO: TCP socket close down on end-of-logging
@@ -97,12 +138,12 @@ static mut OpenLogProgramName: Option<CString> = None;
 static mut OpenLogDoNotLogToStandardError: bool = true;
 
 #[cfg(any(target_os = "windows", target_os = "solaris"))]
-static mut OpenLogDefaultFacility: Facility = Default::default();
+static mut OpenLogDefaultFacility: Facility = Facility::LOG_USER;
 
 #[cfg(any(target_os = "windows", target_os = "solaris"))]
-pub fn log_to_standard_error_for_windows_and_solaris_bytes(priority: Priority, message: &[u8])
+pub fn log_to_standard_error_for_windows_and_solaris_bytes(priority: LocalPriority, message: &[u8])
 {
-	let message = unsafe { CStr.from_bytes_with_nul_unchecked(message) };
+	let message = unsafe { CStr::from_bytes_with_nul_unchecked(message) };
 	log_to_standard_error_for_windows_and_solaris_cstr(priority, message);
 }
 
@@ -118,27 +159,33 @@ pub fn enable_logging_to_standard_error(programName: &CStr, logToStandardErrorAs
 }
 
 #[cfg(any(target_os = "windows", target_os = "solaris"))]
-pub fn log_to_standard_error_for_windows_and_solaris_cstr(priority: Priority, message: &CStr)
+pub fn log_to_standard_error_for_windows_and_solaris_cstr(priority: LocalPriority, message: &CStr)
 {
-	if OpenLogDoNotLogToStandardError
+	use syslogSenders::ConsoleSyslogSender;
+	use syslogSenders::SyslogSender;
+	use rfc5424::StructuredData;
+	
+	if unsafe { OpenLogDoNotLogToStandardError }
 	{
 		return;
 	}
 	
-	let message: &str = message.to_string_lossy();
-	let timeNow = time::now_utc();
-	let messageFacility = priority.facility();
-	
 	let chosenFacility = match priority.facility()
 	{
-		Facility::LOG_KERN => OpenLogDefaultFacility,
-		everythingElse @ _ => everythingElse,
+		None | Some(Facility::LOG_KERN) => unsafe { OpenLogDefaultFacility },
+		Some(everythingElse) => everythingElse,
+	};
+	
+	let programName = match unsafe { &OpenLogProgramName }
+	{
+		&Some(ref programName) => programName.to_string_lossy().into_owned(),
+		&None => String::new(),
 	};
 	
-	let message = format_message_rfc3164(&CurrentProcess.hostName, &OpenLogProgramName, &CurrentProcess.pid, timeNow, chosenFacility.toRfc3164Facility(), priority.severity(), message);
+	let consoleSyslogSender = ConsoleSyslogSender::new(&programName, "");
 	
-	// We do not use the writeln! macro here, as it is not clear that it gets the line delimiter correct (and after the breaking changes to the lines() logic in rust, I no longer trust the std library in this area)
-	write!(&mut w, "{}\r\n", message).unwrap();
+	// There is nowhere to report a failure to write to standard error
+	let _ = consoleSyslogSender.send(chosenFacility.toRfc3164Facility(), priority.severity(), &StructuredData::new(), &message.to_string_lossy());
 }
 
 cfg_if!
@@ -0,0 +1,60 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate libc;
+use self::libc::c_int;
+use Severity;
+use Facility;
+
+#[cfg(not(target_os = "windows"))]
+const LOG_PRIMASK:c_int = self::libc::LOG_PRIMASK;
+#[cfg(target_os = "windows")]
+const LOG_PRIMASK:c_int = 7;
+
+#[cfg(not(target_os = "windows"))]
+const LOG_FACMASK:c_int = self::libc::LOG_FACMASK;
+#[cfg(target_os = "windows")]
+const LOG_FACMASK:c_int = 0x3f8;
+
+
+/// A platform-local priority, as passed to libc's `syslog()`
+/// Unlike `Priority`, the facility bits are those of the local libc's `Facility`, which differ from RFC 3164 on some platforms (eg Solaris' `LOG_CRON`)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct LocalPriority(c_int);
+
+impl LocalPriority
+{
+	#[inline(always)]
+	pub const fn new(facility: Facility, severity: Severity) -> LocalPriority
+	{
+		LocalPriority((facility as c_int) | (severity as c_int))
+	}
+	
+	/// No facility bits are set, so the facility passed to `openlog()` is used
+	#[inline(always)]
+	pub const fn forCurrentLoggingFacility(severity: Severity) -> LocalPriority
+	{
+		LocalPriority(severity as c_int)
+	}
+	
+	#[inline(always)]
+	pub fn value(self) -> c_int
+	{
+		self.0
+	}
+	
+	/// Replicates the behaviour of the C 'function' macro LOG_PRI, but safely
+	#[inline(always)]
+	pub fn severity(self) -> Severity
+	{
+		Severity::fromLocal(self.0 & LOG_PRIMASK).expect("LOG_PRIMASK only permits defined severities")
+	}
+	
+	/// Unlike the C 'function' macro LOG_FAC, the result is not shifted, as `Facility` values are pre-shifted
+	/// Returns None if the facility bits are not known on this platform
+	#[inline(always)]
+	pub fn facility(self) -> Option<Facility>
+	{
+		Facility::fromLocal(self.0 & LOG_FACMASK)
+	}
+}
@@ -0,0 +1,26 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate log;
+use self::log::SetLoggerError;
+use self::log::set_boxed_logger;
+use self::log::set_max_level;
+use logFacade::LevelToSeverityMapping;
+use logFacade::SyslogLogger;
+use logFacade::TargetUsage;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use active_log_mask;
+
+
+/// Installs a `SyslogLogger` as the `log` crate's logger, with a maximum level derived from the active log mask
+/// Fails if a logger has already been installed
+pub fn init<S: SyslogSender + Send + Sync + 'static>(sender: S, rfc3164Facility: Rfc3164Facility, levelToSeverityMapping: LevelToSeverityMapping, targetUsage: TargetUsage) -> Result<(), SetLoggerError>
+{
+	let syslogLogger = SyslogLogger::new(sender, rfc3164Facility, levelToSeverityMapping, targetUsage, active_log_mask());
+	let maximumLevelFilter = syslogLogger.maximumLevelFilter();
+	
+	try!(set_boxed_logger(Box::new(syslogLogger)));
+	set_max_level(maximumLevelFilter);
+	Ok(())
+}
@@ -0,0 +1,62 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate log;
+use self::log::Level;
+use Severity;
+
+
+/// Maps `log::Level` to `Severity`; there is no level for `LOG_EMERG`, `LOG_ALERT`, `LOG_CRIT` or `LOG_NOTICE` unless mapped here
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct LevelToSeverityMapping
+{
+	pub error: Severity,
+	pub warn: Severity,
+	pub info: Severity,
+	pub debug: Severity,
+	pub trace: Severity,
+}
+
+impl LevelToSeverityMapping
+{
+	#[inline(always)]
+	pub fn severity(&self, level: Level) -> Severity
+	{
+		match level
+		{
+			Level::Error => self.error,
+			Level::Warn => self.warn,
+			Level::Info => self.info,
+			Level::Debug => self.debug,
+			Level::Trace => self.trace,
+		}
+	}
+}
+
+impl Default for LevelToSeverityMapping
+{
+	/// Defaults to the obvious mapping, with `Trace` also mapped to `LOG_DEBUG`
+	#[inline(always)]
+	fn default() -> LevelToSeverityMapping
+	{
+		LevelToSeverityMapping
+		{
+			error: Severity::LOG_ERR,
+			warn: Severity::LOG_WARNING,
+			info: Severity::LOG_INFO,
+			debug: Severity::LOG_DEBUG,
+			trace: Severity::LOG_DEBUG,
+		}
+	}
+}
+
+#[test]
+fn defaultMappingFoldsTraceIntoDebug()
+{
+	let levelToSeverityMapping = LevelToSeverityMapping::default();
+	assert_eq!(levelToSeverityMapping.severity(Level::Error), Severity::LOG_ERR);
+	assert_eq!(levelToSeverityMapping.severity(Level::Warn), Severity::LOG_WARNING);
+	assert_eq!(levelToSeverityMapping.severity(Level::Info), Severity::LOG_INFO);
+	assert_eq!(levelToSeverityMapping.severity(Level::Debug), Severity::LOG_DEBUG);
+	assert_eq!(levelToSeverityMapping.severity(Level::Trace), Severity::LOG_DEBUG);
+}
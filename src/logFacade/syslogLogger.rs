@@ -0,0 +1,153 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate log;
+use self::log::Level;
+use self::log::LevelFilter;
+use self::log::Log;
+use self::log::Metadata;
+use self::log::Record;
+use logFacade::LevelToSeverityMapping;
+use logFacade::TargetUsage;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use rfc5424::StructuredData;
+use rfc5424::StructuredDataElement;
+use LogMask;
+use MessageHeaderOverrides;
+
+
+/// A `log::Log` that sends records using any `SyslogSender`
+/// The module path, file and line of each record are attached as a `source@38188` structured data element
+#[derive(Debug)]
+pub struct SyslogLogger<S: SyslogSender>
+{
+	sender: S,
+	rfc3164Facility: Rfc3164Facility,
+	levelToSeverityMapping: LevelToSeverityMapping,
+	targetUsage: TargetUsage,
+	logMask: LogMask,
+}
+
+impl <S: SyslogSender> SyslogLogger<S>
+{
+	/// Records whose mapped severity is not in `logMask` are discarded
+	pub fn new(sender: S, rfc3164Facility: Rfc3164Facility, levelToSeverityMapping: LevelToSeverityMapping, targetUsage: TargetUsage, logMask: LogMask) -> SyslogLogger<S>
+	{
+		SyslogLogger
+		{
+			sender: sender,
+			rfc3164Facility: rfc3164Facility,
+			levelToSeverityMapping: levelToSeverityMapping,
+			targetUsage: targetUsage,
+			logMask: logMask,
+		}
+	}
+	
+	/// The most verbose level whose mapped severity is in the log mask, suitable for `log::set_max_level()`
+	pub fn maximumLevelFilter(&self) -> LevelFilter
+	{
+		for level in &[Level::Trace, Level::Debug, Level::Info, Level::Warn, Level::Error]
+		{
+			if self.isLevelEnabled(*level)
+			{
+				return level.to_level_filter();
+			}
+		}
+		LevelFilter::Off
+	}
+	
+	#[inline(always)]
+	fn isLevelEnabled(&self, level: Level) -> bool
+	{
+		self.logMask.contains(self.levelToSeverityMapping.severity(level))
+	}
+}
+
+impl <S: SyslogSender + Send + Sync> Log for SyslogLogger<S>
+{
+	#[inline(always)]
+	fn enabled(&self, metadata: &Metadata) -> bool
+	{
+		self.isLevelEnabled(metadata.level())
+	}
+	
+	fn log(&self, record: &Record)
+	{
+		if !self.enabled(record.metadata())
+		{
+			return;
+		}
+		
+		let severity = self.levelToSeverityMapping.severity(record.level());
+		
+		let target = record.target();
+		let messageHeaderOverrides = match self.targetUsage
+		{
+			TargetUsage::MessageId => MessageHeaderOverrides::messageId(target),
+			TargetUsage::AppName => MessageHeaderOverrides::appName(target),
+			TargetUsage::Ignore => MessageHeaderOverrides::default(),
+		};
+		
+		let mut structuredData = StructuredData::new();
+		// The only element, so never a duplicate
+		let _ = structuredData.insert(StructuredDataElement::source(record.module_path(), record.file(), record.line()));
+		
+		let message = record.args().to_string();
+		
+		// log::Log has no way to report a failure
+		let _ = self.sender.sendWithOverrides(self.rfc3164Facility, severity, &messageHeaderOverrides, &structuredData, &message);
+	}
+	
+	#[inline(always)]
+	fn flush(&self)
+	{
+	}
+}
+
+#[test]
+fn mapsLevelsUsesTargetsAndChecksTheMask()
+{
+	use syslogSenders::CapturingSyslogSender;
+	use Severity;
+	
+	let levelToSeverityMapping = LevelToSeverityMapping
+	{
+		warn: Severity::LOG_NOTICE,
+		.. LevelToSeverityMapping::default()
+	};
+	let newLogger = |targetUsage| SyslogLogger::new(CapturingSyslogSender::forTesting(), Rfc3164Facility::local0, levelToSeverityMapping, targetUsage, LogMask::upto(Severity::LOG_INFO));
+	
+	let syslogLogger = newLogger(TargetUsage::MessageId);
+	assert!(syslogLogger.enabled(&Metadata::builder().level(Level::Info).target("target").build()));
+	assert!(!syslogLogger.enabled(&Metadata::builder().level(Level::Debug).target("target").build()));
+	assert_eq!(syslogLogger.maximumLevelFilter(), LevelFilter::Info);
+	
+	syslogLogger.log(&Record::builder().args(format_args!("warned")).level(Level::Warn).target("my::target").module_path(Some("my::module")).file(Some("src/my/module.rs")).line(Some(7)).build());
+	syslogLogger.log(&Record::builder().args(format_args!("debugged")).level(Level::Debug).target("my::target").build());
+	
+	let capturedMessages = syslogLogger.sender.messages();
+	assert_eq!(capturedMessages.len(), 1);
+	assert_eq!(capturedMessages[0].message, "warned");
+	assert_eq!(capturedMessages[0].severity, Severity::LOG_NOTICE);
+	assert_eq!(capturedMessages[0].rfc3164Facility, Rfc3164Facility::local0);
+	assert_eq!(capturedMessages[0].messageId, Some("my::target".to_owned()));
+	assert_eq!(capturedMessages[0].appName, None);
+	assert_eq!(capturedMessages[0].structuredDataParameter("source@38188", "modulePath"), Some("my::module"));
+	assert_eq!(capturedMessages[0].structuredDataParameter("source@38188", "line"), Some("7"));
+	
+	let syslogLogger = newLogger(TargetUsage::AppName);
+	syslogLogger.log(&Record::builder().args(format_args!("informed")).level(Level::Info).target("my::target").build());
+	let capturedMessage = syslogLogger.sender.messages().pop().unwrap();
+	assert_eq!(capturedMessage.appName, Some("my::target".to_owned()));
+	assert_eq!(capturedMessage.messageId, None);
+	
+	let syslogLogger = newLogger(TargetUsage::Ignore);
+	syslogLogger.log(&Record::builder().args(format_args!("informed")).level(Level::Info).target("my::target").build());
+	let capturedMessage = syslogLogger.sender.messages().pop().unwrap();
+	assert_eq!(capturedMessage.appName, None);
+	assert_eq!(capturedMessage.messageId, None);
+	
+	let nothingEnabled = SyslogLogger::new(CapturingSyslogSender::forTesting(), Rfc3164Facility::local0, LevelToSeverityMapping::default(), TargetUsage::Ignore, LogMask::empty());
+	assert_eq!(nothingEnabled.maximumLevelFilter(), LevelFilter::Off);
+}
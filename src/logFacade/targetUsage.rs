@@ -0,0 +1,25 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+
+/// How a `log::Record`'s target (by default, its module path) is used in the message header
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TargetUsage
+{
+	/// Truncated to 32 characters; ignored by RFC 3164 senders
+	MessageId,
+	
+	/// Truncated to 48 characters (32 for RFC 3164)
+	AppName,
+	
+	Ignore,
+}
+
+impl Default for TargetUsage
+{
+	#[inline(always)]
+	fn default() -> TargetUsage
+	{
+		TargetUsage::MessageId
+	}
+}
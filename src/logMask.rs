@@ -3,40 +3,254 @@
 
 extern crate libc;
 use self::libc::c_int;
+use std::ops::BitAnd;
+use std::ops::BitAndAssign;
+use std::ops::BitOr;
+use std::ops::BitOrAssign;
+use std::ops::Not;
+use std::ops::Sub;
+use std::ops::SubAssign;
 use Severity;
 
-pub type LogMask = c_int;
+
+/// A set of severities, as used by libc's `setlogmask()`; a severity is logged if it is contained in the mask
+/// Hand-written rather than generated with `bitflags!`, as `contains()` takes a `Severity` rather than another mask
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct LogMask(c_int);
+
+impl LogMask
+{
+	const AllBits: c_int = 0xFF;
+	
+	#[inline(always)]
+	pub const fn empty() -> LogMask
+	{
+		LogMask(0)
+	}
+	
+	#[inline(always)]
+	pub const fn all() -> LogMask
+	{
+		LogMask(LogMask::AllBits)
+	}
+	
+	/// Bits other than those for the eight severities are discarded
+	#[inline(always)]
+	pub const fn from_bits_truncate(bits: c_int) -> LogMask
+	{
+		LogMask(bits & LogMask::AllBits)
+	}
+	
+	#[inline(always)]
+	pub fn bits(self) -> c_int
+	{
+		self.0
+	}
+	
+	/// `severity` and all severities more severe than it, eg `upto(LOG_ERR)` contains LOG_EMERG, LOG_ALERT, LOG_CRIT and LOG_ERR
+	/// Equivalent to the C 'function' macro LOG_UPTO
+	#[inline(always)]
+	pub const fn upto(severity: Severity) -> LogMask
+	{
+		severity.mask_at_least_as_severe_as()
+	}
+	
+	#[inline(always)]
+	pub const fn exactly(severity: Severity) -> LogMask
+	{
+		severity.mask()
+	}
+	
+	#[inline(always)]
+	pub const fn all_except(severity: Severity) -> LogMask
+	{
+		severity.mask_all_but_self()
+	}
+	
+	#[inline(always)]
+	pub fn contains(self, severity: Severity) -> bool
+	{
+		self.0 & severity.mask().0 != 0
+	}
+	
+	#[inline(always)]
+	pub fn is_empty(self) -> bool
+	{
+		self.0 == 0
+	}
+	
+	#[inline(always)]
+	pub fn insert(&mut self, severity: Severity)
+	{
+		self.0 |= severity.mask().0;
+	}
+	
+	#[inline(always)]
+	pub fn remove(&mut self, severity: Severity)
+	{
+		self.0 &= !severity.mask().0;
+	}
+	
+	#[inline(always)]
+	pub fn union(self, other: LogMask) -> LogMask
+	{
+		LogMask(self.0 | other.0)
+	}
+	
+	#[inline(always)]
+	pub fn intersection(self, other: LogMask) -> LogMask
+	{
+		LogMask(self.0 & other.0)
+	}
+	
+	#[inline(always)]
+	pub fn difference(self, other: LogMask) -> LogMask
+	{
+		LogMask(self.0 & !other.0)
+	}
+	
+	#[inline(always)]
+	pub fn complement(self) -> LogMask
+	{
+		LogMask(!self.0 & LogMask::AllBits)
+	}
+	
+	/// Sets the priority mask used by libc's `syslog()`, and returns the old mask
+	/// If never called, then the default LogMask allows all levels
+	/// This has no effect on senders other than `PosixSyslogSender`; wrap those in a `MaskingSyslogSender`
+	/// Always inlined as it is just a wrapper around the C function
+	#[inline(always)]
+	pub fn set_mask(self) -> LogMask
+	{
+		LogMask::from_bits_truncate(unsafe { self::libc::setlogmask(self.0) })
+	}
+}
+
+impl Default for LogMask
+{
+	/// Defaults to all severities, as does libc
+	#[inline(always)]
+	fn default() -> LogMask
+	{
+		LogMask::all()
+	}
+}
+
+impl BitOr for LogMask
+{
+	type Output = LogMask;
+	
+	#[inline(always)]
+	fn bitor(self, other: LogMask) -> LogMask
+	{
+		self.union(other)
+	}
+}
+
+impl BitOrAssign for LogMask
+{
+	#[inline(always)]
+	fn bitor_assign(&mut self, other: LogMask)
+	{
+		*self = self.union(other);
+	}
+}
+
+impl BitAnd for LogMask
+{
+	type Output = LogMask;
+	
+	#[inline(always)]
+	fn bitand(self, other: LogMask) -> LogMask
+	{
+		self.intersection(other)
+	}
+}
+
+impl BitAndAssign for LogMask
+{
+	#[inline(always)]
+	fn bitand_assign(&mut self, other: LogMask)
+	{
+		*self = self.intersection(other);
+	}
+}
+
+impl Sub for LogMask
+{
+	type Output = LogMask;
+	
+	#[inline(always)]
+	fn sub(self, other: LogMask) -> LogMask
+	{
+		self.difference(other)
+	}
+}
+
+impl SubAssign for LogMask
+{
+	#[inline(always)]
+	fn sub_assign(&mut self, other: LogMask)
+	{
+		*self = self.difference(other);
+	}
+}
+
+impl Not for LogMask
+{
+	type Output = LogMask;
+	
+	#[inline(always)]
+	fn not(self) -> LogMask
+	{
+		self.complement()
+	}
+}
 
 #[cfg(debug_assertions)]
 #[inline(always)]
 pub fn default_log_mask() -> LogMask
 {
-	Severity::LOG_DEBUG.mask()
+	LogMask::upto(Severity::LOG_DEBUG)
 }
 
 #[cfg(not(debug_assertions))]
 #[inline(always)]
 pub fn default_log_mask() -> LogMask
 {
-	Severity::LOG_INFO.mask()
+	LogMask::upto(Severity::LOG_INFO)
 }
 
 
-pub trait LogMaskTrait
+/// The mask most recently set with `set_mask()` (or by C code calling `setlogmask()`)
+#[inline(always)]
+pub fn active_log_mask() -> LogMask
 {
-	/// Sets the priority mask, and returns the old mask
-	/// If never called, then the default LogMask allows all levels
-	fn set_mask(self) -> LogMask;
+	// A mask of zero does not change the mask
+	LogMask::from_bits_truncate(unsafe { self::libc::setlogmask(0) })
 }
 
-impl LogMaskTrait for LogMask
+#[test]
+fn uptoContainsSeverityAndThoseMoreSevere()
 {
-	/// Sets the priority mask, and returns the old mask
-	/// If never called, then the default LogMask allows all levels
-	/// Always inlined as it is just a wrapper around the C function
-	#[inline(always)]
-	fn set_mask(self) -> LogMask
-	{
-		unsafe { self::libc::setlogmask(self) }
-	}
-}
\ No newline at end of file
+	let mask = LogMask::upto(Severity::LOG_ERR);
+	assert!(mask.contains(Severity::LOG_EMERG));
+	assert!(mask.contains(Severity::LOG_ERR));
+	assert!(!mask.contains(Severity::LOG_WARNING));
+	
+	assert_eq!((mask | LogMask::exactly(Severity::LOG_DEBUG)) - LogMask::exactly(Severity::LOG_EMERG), LogMask::from_bits_truncate(0b1000_1110));
+	assert_eq!(!LogMask::all_except(Severity::LOG_INFO), LogMask::exactly(Severity::LOG_INFO));
+	assert!(default_log_mask().contains(Severity::LOG_INFO));
+}
+
+#[test]
+fn defaultLogMaskIncludesEveryMoreSevereSeverity()
+{
+	let mask = default_log_mask();
+	assert!(mask.contains(Severity::LOG_EMERG));
+	assert!(mask.contains(Severity::LOG_ERR));
+	assert!(mask.contains(Severity::LOG_INFO));
+	assert_eq!(mask.contains(Severity::LOG_DEBUG), cfg!(debug_assertions));
+	assert_ne!(mask, Severity::LOG_DEBUG.mask());
+	assert_ne!(mask, Severity::LOG_INFO.mask());
+}
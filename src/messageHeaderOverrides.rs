@@ -0,0 +1,35 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+
+/// Per-message replacements for header fields that a `SyslogSender` otherwise takes from its `SyslogRfc`
+/// Fields which are `None` are left as constructed; fields not present in a format (eg MSGID in RFC 3164) are ignored
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct MessageHeaderOverrides<'a>
+{
+	pub appName: Option<&'a str>,
+	pub messageId: Option<&'a str>,
+}
+
+impl <'a> MessageHeaderOverrides<'a>
+{
+	#[inline(always)]
+	pub fn appName(appName: &'a str) -> MessageHeaderOverrides<'a>
+	{
+		MessageHeaderOverrides
+		{
+			appName: Some(appName),
+			messageId: None,
+		}
+	}
+	
+	#[inline(always)]
+	pub fn messageId(messageId: &'a str) -> MessageHeaderOverrides<'a>
+	{
+		MessageHeaderOverrides
+		{
+			appName: None,
+			messageId: Some(messageId),
+		}
+	}
+}
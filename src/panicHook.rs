@@ -0,0 +1,136 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::any::Any;
+use std::backtrace::Backtrace;
+use std::cell::Cell;
+use std::panic::Location;
+use std::panic::PanicHookInfo;
+use std::panic::set_hook;
+use std::panic::take_hook;
+use std::thread;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use rfc5424::StructuredData;
+use rfc5424::StructuredDataElement;
+use Severity;
+
+
+/// Logs every panic, on every thread, using `sender`, and then calls the previously installed hook (by default, the one that prints to standard error)
+/// Unlike `with_open_syslog2`, this logs panics that do not unwind through a particular closure, and those in programs built with `panic = "abort"`
+/// The thread name, file, line and, if `captureBacktrace` is true, a backtrace are attached as a `panic@38188` element
+/// A panic whilst logging a panic on the same thread, eg in `sender`, is not logged, so that the hook does not recurse
+/// Typically used with `Severity::LOG_CRIT`
+pub fn install_panic_hook<S: SyslogSender + Send + Sync + 'static>(sender: S, rfc3164Facility: Rfc3164Facility, severity: Severity, captureBacktrace: bool)
+{
+	let previousHook = take_hook();
+	set_hook(Box::new(move |panicInfo|
+	{
+		unless_reentered(|| log_panic(&sender, rfc3164Facility, severity, captureBacktrace, panicInfo.location(), panicInfo.payload()));
+		previousHook(panicInfo);
+	}));
+}
+
+thread_local!
+{
+	static InPanicHook: Cell<bool> = Cell::new(false);
+}
+
+struct InPanicHookGuard;
+
+impl Drop for InPanicHookGuard
+{
+	fn drop(&mut self)
+	{
+		let _ = InPanicHook.try_with(|inPanicHook| inPanicHook.set(false));
+	}
+}
+
+/// Does not call `log` if already within it on this thread, or if the thread is being torn down
+fn unless_reentered<F: FnOnce()>(log: F)
+{
+	match InPanicHook.try_with(|inPanicHook| inPanicHook.replace(true))
+	{
+		Ok(false) =>
+		{
+			let _guard = InPanicHookGuard;
+			log();
+		},
+		_ => (),
+	}
+}
+
+fn log_panic<S: SyslogSender>(sender: &S, rfc3164Facility: Rfc3164Facility, severity: Severity, captureBacktrace: bool, location: Option<&Location>, payload: &(dyn Any + Send))
+{
+	let currentThread = thread::current();
+	let threadName = currentThread.name().unwrap_or("<unnamed>");
+	
+	let file = location.map(|location| location.file());
+	let line = location.map(|location| location.line());
+	
+	let backtrace = if captureBacktrace
+	{
+		Some(Backtrace::force_capture().to_string())
+	}
+	else
+	{
+		None
+	};
+	
+	let payload = panic_payload(payload);
+	let message = match location
+	{
+		None => format!("thread '{}' panicked: {}", threadName, payload),
+		Some(location) => format!("thread '{}' panicked at {}:{}: {}", threadName, location.file(), location.line(), payload),
+	};
+	
+	let mut structuredData = StructuredData::new();
+	// The only element, so never a duplicate
+	let _ = structuredData.insert(StructuredDataElement::panic(threadName, file, line, backtrace));
+	
+	// There is nowhere to report a failure whilst panicking
+	let _ = sender.send(rfc3164Facility, severity, &structuredData, &message);
+}
+
+fn panic_payload(payload: &(dyn Any + Send)) -> &str
+{
+	if let Some(string) = payload.downcast_ref::<&str>()
+	{
+		return string;
+	}
+	
+	if let Some(string) = payload.downcast_ref::<String>()
+	{
+		return string;
+	}
+	
+	"(data unformattable)"
+}
+
+#[test]
+fn logsPanicsWithoutReentering()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let sender = CapturingSyslogSender::forTesting();
+	let location = Location::caller();
+	let payload: Box<dyn Any + Send> = Box::new("boom".to_owned());
+	
+	unless_reentered(||
+	{
+		log_panic(&sender, Rfc3164Facility::user, Severity::LOG_CRIT, false, Some(location), &*payload);
+		unless_reentered(|| log_panic(&sender, Rfc3164Facility::user, Severity::LOG_CRIT, false, Some(location), &*payload));
+	});
+	assert_eq!(sender.len(), 1);
+	
+	// Logged again once the first has finished
+	unless_reentered(|| log_panic(&sender, Rfc3164Facility::user, Severity::LOG_CRIT, false, None, &"bang"));
+	assert_eq!(sender.len(), 2);
+	
+	let capturedMessages = sender.messages();
+	let threadName = thread::current().name().unwrap().to_owned();
+	assert_eq!(capturedMessages[0].message, format!("thread '{}' panicked at {}:{}: boom", threadName, location.file(), location.line()));
+	assert_eq!(capturedMessages[0].severity, Severity::LOG_CRIT);
+	assert_eq!(capturedMessages[0].structuredDataParameter("panic@38188", "line"), Some(&location.line().to_string()[..]));
+	assert_eq!(capturedMessages[1].message, format!("thread '{}' panicked: bang", threadName));
+}
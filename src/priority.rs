@@ -1,58 +1,150 @@
 // This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
 // Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
 
-extern crate libc;
-use self::libc::c_int;
-use std::mem::transmute;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
 use Severity;
-use Facility;
+use LocalPriority;
+use PriorityParseError;
+use syslogSenders::Rfc3164Facility;
 
-#[cfg(not(target_os = "windows"))]
-const LOG_PRIMASK:c_int = self::libc::LOG_PRIMASK;
-#[cfg(target_os = "windows")]
-const LOG_PRIMASK:c_int = 7;
 
-#[cfg(not(target_os = "windows"))]
-const LOG_FACMASK:c_int = self::libc::LOG_FACMASK;
-#[cfg(target_os = "windows")]
-const LOG_FACMASK:c_int = 0x3f8;
-
-pub type Priority = c_int;
-
-/// Always inlined to replicate the behaviour of a C 'function' macro
-#[inline(always)]
-fn LOG_PRI(p: Priority) -> Severity
-{
-	unsafe { transmute((p) & LOG_PRIMASK) }
-}
-
-/// Always inlined to replicate the behaviour of a C 'function' macro
-#[inline(always)]
-fn LOG_FAC(p: Priority) -> Facility
+/// A validated RFC 3164 / RFC 5424 PRI value, as sent on the wire
+/// This is not the same as the platform-local value passed to libc's `syslog()`; for that, see `LocalPriority`
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Priority
 {
-	unsafe { transmute((p & LOG_FACMASK) >> 3) }
+	rfc3164Facility: Rfc3164Facility,
+	severity: Severity,
 }
 
-pub trait PriorityTrait
+impl Priority
 {
-	fn severity(self) -> Severity;
+	/// The largest PRI value permitted by RFC 5424, ie local7.debug
+	pub const MaximumWireValue: i32 = 191;
+	
+	#[inline(always)]
+	pub const fn new(rfc3164Facility: Rfc3164Facility, severity: Severity) -> Priority
+	{
+		Priority
+		{
+			rfc3164Facility: rfc3164Facility,
+			severity: severity,
+		}
+	}
+	
+	/// Returns None if `wire` is out of range or uses a private use facility (see `Rfc3164Facility::from()`)
+	pub fn fromWire(wire: i32) -> Option<Priority>
+	{
+		if wire < 0 || wire > Priority::MaximumWireValue
+		{
+			return None;
+		}
+		
+		match Rfc3164Facility::from(wire >> 3)
+		{
+			None => None,
+			Some(rfc3164Facility) => Severity::fromWire(wire & 0x07).map(|severity| Priority::new(rfc3164Facility, severity)),
+		}
+	}
+	
+	#[inline(always)]
+	pub fn toWire(self) -> i32
+	{
+		((self.rfc3164Facility as i32) << 3) | self.severity.toWire()
+	}
+	
+	#[inline(always)]
+	pub fn rfc3164Facility(self) -> Rfc3164Facility
+	{
+		self.rfc3164Facility
+	}
 	
-	fn facility(self) -> Facility;
+	#[inline(always)]
+	pub fn severity(self) -> Severity
+	{
+		self.severity
+	}
+	
+	/// Maps Rfc3164Facility::ftp to `LOG_DAEMON` on Solaris
+	#[inline(always)]
+	pub fn toLocalPriority(self) -> LocalPriority
+	{
+		self.severity.toPriority(self.rfc3164Facility.toFacilityMappingSolarisToDaemon())
+	}
 }
 
-impl PriorityTrait for Priority
+impl Display for Priority
 {
-	/// Always inlined to replicate the behaviour of a C 'function' macro
-	#[inline(always)]
-	fn severity(self) -> Severity
+	/// Formats as `<PRI>`, eg `<134>` for `local0.info`
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result
 	{
-		LOG_PRI(self)
+		write!(formatter, "<{}>", self.toWire())
 	}
+}
+
+impl FromStr for Priority
+{
+	type Err = PriorityParseError;
 	
-	/// Always inlined to replicate the behaviour of a C 'function' macro
-	#[inline(always)]
-	fn facility(self) -> Facility
+	/// Parses either the wire form, `<134>`, or the selector-like form, `local0.info`
+	fn from_str(value: &str) -> Result<Self, Self::Err>
 	{
-		LOG_FAC(self)
+		if value.is_empty()
+		{
+			return Err(PriorityParseError::Empty);
+		}
+		
+		if value.starts_with('<')
+		{
+			if !value.ends_with('>') || value.len() < 3
+			{
+				return Err(PriorityParseError::Malformed(value.to_owned()));
+			}
+			
+			let digits = &value[1 .. value.len() - 1];
+			if digits.len() > 3 || !digits.bytes().all(|byte| byte >= b'0' && byte <= b'9')
+			{
+				return Err(PriorityParseError::Malformed(value.to_owned()));
+			}
+			
+			let wire = digits.parse::<i32>().unwrap();
+			if wire > Priority::MaximumWireValue
+			{
+				return Err(PriorityParseError::OutOfRange(wire));
+			}
+			return Priority::fromWire(wire).ok_or(PriorityParseError::PrivateUseFacility(wire >> 3));
+		}
+		
+		let mut parts = value.splitn(2, '.');
+		let facilityName = parts.next().unwrap();
+		let severityName = match parts.next()
+		{
+			None => return Err(PriorityParseError::Malformed(value.to_owned())),
+			Some(severityName) => severityName,
+		};
+		
+		let rfc3164Facility = try!(Rfc3164Facility::fromName(facilityName).ok_or_else(|| PriorityParseError::UnknownFacility(facilityName.to_owned())));
+		let severity = try!(Severity::fromName(severityName).ok_or_else(|| PriorityParseError::UnknownSeverity(severityName.to_owned())));
+		Ok(Priority::new(rfc3164Facility, severity))
 	}
 }
+
+#[test]
+fn parsesAndFormatsWireAndNamedForms()
+{
+	let priority = Priority::new(Rfc3164Facility::local0, Severity::LOG_INFO);
+	assert_eq!(priority.toWire(), 134);
+	assert_eq!(priority.to_string(), "<134>");
+	assert_eq!("<134>".parse::<Priority>(), Ok(priority));
+	assert_eq!("local0.info".parse::<Priority>(), Ok(priority));
+	assert_eq!(Priority::fromWire(134), Some(priority));
+	
+	assert_eq!(Priority::fromWire(192), None);
+	assert_eq!(Priority::fromWire(12 << 3), None);
+	assert_eq!("<192>".parse::<Priority>(), Err(PriorityParseError::OutOfRange(192)));
+	assert_eq!("<13".parse::<Priority>(), Err(PriorityParseError::Malformed("<13".to_owned())));
+	assert_eq!("local0.loud".parse::<Priority>(), Err(PriorityParseError::UnknownSeverity("loud".to_owned())));
+}
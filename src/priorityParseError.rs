@@ -0,0 +1,42 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PriorityParseError
+{
+	Empty,
+	Malformed(String),
+	OutOfRange(i32),
+	
+	/// RFC 3164 facility codes 12 - 15 inclusive are not supported by `Rfc3164Facility`
+	PrivateUseFacility(i32),
+	
+	UnknownFacility(String),
+	UnknownSeverity(String),
+}
+
+impl Display for PriorityParseError
+{
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result
+	{
+		match *self
+		{
+			PriorityParseError::Empty => write!(formatter, "priority is empty"),
+			PriorityParseError::Malformed(ref value) => write!(formatter, "priority '{}' is neither of the form '<PRI>' nor 'facility.severity'", value),
+			PriorityParseError::OutOfRange(wire) => write!(formatter, "priority {} is greater than 191", wire),
+			PriorityParseError::PrivateUseFacility(facility) => write!(formatter, "facility {} is private use", facility),
+			PriorityParseError::UnknownFacility(ref name) => write!(formatter, "facility '{}' is not known", name),
+			PriorityParseError::UnknownSeverity(ref name) => write!(formatter, "severity '{}' is not known", name),
+		}
+	}
+}
+
+impl Error for PriorityParseError
+{
+}
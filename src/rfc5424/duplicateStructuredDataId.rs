@@ -0,0 +1,36 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::io;
+use std::io::ErrorKind;
+
+
+/// Returned by `StructuredData::insert()` under `DuplicateStructuredDataIdPolicy::Reject`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DuplicateStructuredDataId(pub String);
+
+impl Display for DuplicateStructuredDataId
+{
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result
+	{
+		write!(formatter, "an element with SD-ID '{}' is already present", self.0)
+	}
+}
+
+impl Error for DuplicateStructuredDataId
+{
+}
+
+/// So that senders can use `try!` when annotating structured data
+impl From<DuplicateStructuredDataId> for io::Error
+{
+	#[inline(always)]
+	fn from(error: DuplicateStructuredDataId) -> io::Error
+	{
+		io::Error::new(ErrorKind::InvalidInput, error)
+	}
+}
@@ -0,0 +1,29 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+
+/// What `StructuredData::insert()` does when an element with the same SD-ID is already present; RFC 5424 section 6.3.2 forbids the same SD-ID appearing more than once in a message
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DuplicateStructuredDataIdPolicy
+{
+	/// The new element is not inserted, and `StructuredData::insert()` returns an error
+	Reject,
+	
+	/// The new element is silently discarded
+	KeepFirst,
+	
+	/// The new element replaces the existing one, in the existing one's position
+	Replace,
+	
+	/// The new element's parameters are appended to the existing one's
+	Merge,
+}
+
+impl Default for DuplicateStructuredDataIdPolicy
+{
+	#[inline(always)]
+	fn default() -> DuplicateStructuredDataIdPolicy
+	{
+		DuplicateStructuredDataIdPolicy::Reject
+	}
+}
@@ -0,0 +1,95 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+
+/// The value of an `origin` element's `enterpriseId` parameter (RFC 5424 section 7.2.2), eg `32473.1.2`
+/// This is an IANA private enterprise number, optionally followed by sub-identifiers; it is not prefixed with `1.3.6.1.4.1.`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnterpriseId
+{
+	privateEnterpriseNumber: u32,
+	subIdentifiers: Vec<u32>,
+}
+
+impl EnterpriseId
+{
+	/// StormMQ's private enterprise number is 38188, for instance
+	#[inline(always)]
+	pub fn new(privateEnterpriseNumber: u32) -> EnterpriseId
+	{
+		EnterpriseId
+		{
+			privateEnterpriseNumber: privateEnterpriseNumber,
+			subIdentifiers: Vec::new(),
+		}
+	}
+	
+	/// Appends a sub-identifier, eg `EnterpriseId::new(32473).subIdentifier(1).subIdentifier(2)` is `32473.1.2`
+	#[inline(always)]
+	pub fn subIdentifier(mut self, subIdentifier: u32) -> EnterpriseId
+	{
+		self.subIdentifiers.push(subIdentifier);
+		self
+	}
+	
+	#[inline(always)]
+	pub fn privateEnterpriseNumber(&self) -> u32
+	{
+		self.privateEnterpriseNumber
+	}
+	
+	#[inline(always)]
+	pub fn subIdentifiers(&self) -> &[u32]
+	{
+		&self.subIdentifiers
+	}
+}
+
+impl Display for EnterpriseId
+{
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result
+	{
+		try!(write!(formatter, "{}", self.privateEnterpriseNumber));
+		for subIdentifier in &self.subIdentifiers
+		{
+			try!(write!(formatter, ".{}", subIdentifier));
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for EnterpriseId
+{
+	type Err = ParseIntError;
+	
+	fn from_str(value: &str) -> Result<Self, Self::Err>
+	{
+		let mut components = value.split('.');
+		
+		// split() always yields at least one component
+		let mut enterpriseId = EnterpriseId::new(try!(components.next().unwrap().parse()));
+		for component in components
+		{
+			enterpriseId.subIdentifiers.push(try!(component.parse()));
+		}
+		Ok(enterpriseId)
+	}
+}
+
+#[test]
+fn displaysAndParsesSubIdentifiers()
+{
+	let enterpriseId = EnterpriseId::new(32473).subIdentifier(1).subIdentifier(2);
+	assert_eq!(enterpriseId.to_string(), "32473.1.2");
+	assert_eq!("32473.1.2".parse::<EnterpriseId>().unwrap(), enterpriseId);
+	assert_eq!("38188".parse::<EnterpriseId>().unwrap(), EnterpriseId::new(38188));
+	assert!("".parse::<EnterpriseId>().is_err());
+	assert!("32473..2".parse::<EnterpriseId>().is_err());
+	assert!("1.3.6.x".parse::<EnterpriseId>().is_err());
+}
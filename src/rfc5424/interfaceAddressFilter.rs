@@ -0,0 +1,160 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate libc;
+use std::net::IpAddr;
+
+
+/// Chooses which network interface addresses are reported in an `origin` element's `ip` parameters
+/// Loopback interfaces and addresses are always excluded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InterfaceAddressFilter
+{
+	/// Interface names, eg `eth0`; if empty, all interfaces not in `excludeInterfaces` are included
+	pub includeInterfaces: Vec<String>,
+	
+	pub excludeInterfaces: Vec<String>,
+	
+	pub includeIpv4: bool,
+	
+	pub includeIpv6: bool,
+	
+	/// IPv4 169.254.0.0/16 and IPv6 fe80::/10 addresses are rarely useful to a log collector
+	pub includeLinkLocal: bool,
+}
+
+impl Default for InterfaceAddressFilter
+{
+	#[inline(always)]
+	fn default() -> InterfaceAddressFilter
+	{
+		InterfaceAddressFilter
+		{
+			includeInterfaces: Vec::new(),
+			excludeInterfaces: Vec::new(),
+			includeIpv4: true,
+			includeIpv6: true,
+			includeLinkLocal: false,
+		}
+	}
+}
+
+impl InterfaceAddressFilter
+{
+	pub fn accepts(&self, interfaceName: &str, ipAddress: &IpAddr) -> bool
+	{
+		if !self.includeInterfaces.is_empty() && !self.includeInterfaces.iter().any(|includeInterface| includeInterface == interfaceName)
+		{
+			return false;
+		}
+		
+		if self.excludeInterfaces.iter().any(|excludeInterface| excludeInterface == interfaceName)
+		{
+			return false;
+		}
+		
+		match *ipAddress
+		{
+			IpAddr::V4(ref ipv4Address) => self.includeIpv4 && !ipv4Address.is_loopback() && !ipv4Address.is_unspecified() && (self.includeLinkLocal || !ipv4Address.is_link_local()),
+			IpAddr::V6(ref ipv6Address) => self.includeIpv6 && !ipv6Address.is_loopback() && !ipv6Address.is_unspecified() && (self.includeLinkLocal || ipv6Address.segments()[0] & 0xFFC0 != 0xFE80),
+		}
+	}
+	
+	/// Enumerates the addresses of interfaces which are up, using `getifaddrs()`, in the order the kernel reports them without duplicates
+	/// Empty if they can not be enumerated
+	#[cfg(unix)]
+	pub fn addresses(&self) -> Vec<IpAddr>
+	{
+		use std::ffi::CStr;
+		use std::net::Ipv4Addr;
+		use std::net::Ipv6Addr;
+		use std::ptr::null_mut;
+		use self::libc::c_int;
+		use self::libc::ifaddrs;
+		use self::libc::getifaddrs;
+		use self::libc::freeifaddrs;
+		use self::libc::sockaddr_in;
+		use self::libc::sockaddr_in6;
+		use self::libc::AF_INET;
+		use self::libc::AF_INET6;
+		use self::libc::IFF_LOOPBACK;
+		use self::libc::IFF_UP;
+		
+		let mut ipAddresses = Vec::new();
+		
+		let mut interfaceAddresses: *mut ifaddrs = null_mut();
+		if unsafe { getifaddrs(&mut interfaceAddresses) } != 0
+		{
+			return ipAddresses;
+		}
+		
+		let mut current = interfaceAddresses;
+		while !current.is_null()
+		{
+			let interfaceAddress = unsafe { &*current };
+			current = interfaceAddress.ifa_next;
+			
+			let flags = interfaceAddress.ifa_flags as c_int;
+			if flags & IFF_UP == 0 || flags & IFF_LOOPBACK != 0 || interfaceAddress.ifa_addr.is_null() || interfaceAddress.ifa_name.is_null()
+			{
+				continue;
+			}
+			
+			let ipAddress = match unsafe { (*interfaceAddress.ifa_addr).sa_family } as c_int
+			{
+				AF_INET =>
+				{
+					let socketAddress = unsafe { &*(interfaceAddress.ifa_addr as *const sockaddr_in) };
+					IpAddr::V4(Ipv4Addr::from(u32::from_be(socketAddress.sin_addr.s_addr)))
+				},
+				AF_INET6 =>
+				{
+					let socketAddress = unsafe { &*(interfaceAddress.ifa_addr as *const sockaddr_in6) };
+					IpAddr::V6(Ipv6Addr::from(socketAddress.sin6_addr.s6_addr))
+				},
+				_ => continue,
+			};
+			
+			let interfaceName = unsafe { CStr::from_ptr(interfaceAddress.ifa_name) }.to_string_lossy();
+			if self.accepts(&interfaceName, &ipAddress) && !ipAddresses.contains(&ipAddress)
+			{
+				ipAddresses.push(ipAddress);
+			}
+		}
+		
+		unsafe { freeifaddrs(interfaceAddresses) };
+		ipAddresses
+	}
+	
+	#[cfg(not(unix))]
+	pub fn addresses(&self) -> Vec<IpAddr>
+	{
+		Vec::new()
+	}
+}
+
+#[test]
+fn excludesLoopbackAndLinkLocal()
+{
+	let filter = InterfaceAddressFilter::default();
+	assert!(!filter.accepts("lo", &"127.0.0.1".parse().unwrap()));
+	assert!(!filter.accepts("eth0", &"::1".parse().unwrap()));
+	assert!(!filter.accepts("eth0", &"169.254.1.1".parse().unwrap()));
+	assert!(!filter.accepts("eth0", &"fe80::1".parse().unwrap()));
+	assert!(filter.accepts("eth0", &"192.0.2.1".parse().unwrap()));
+	assert!(filter.accepts("eth0", &"2001:db8::1".parse().unwrap()));
+	
+	let filter = InterfaceAddressFilter
+	{
+		includeInterfaces: vec!["eth0".to_owned(), "eth1".to_owned()],
+		excludeInterfaces: vec!["eth1".to_owned()],
+		includeIpv6: false,
+		.. InterfaceAddressFilter::default()
+	};
+	assert!(filter.accepts("eth0", &"192.0.2.1".parse().unwrap()));
+	assert!(!filter.accepts("eth0", &"2001:db8::1".parse().unwrap()));
+	assert!(!filter.accepts("eth1", &"192.0.2.2".parse().unwrap()));
+	assert!(!filter.accepts("wlan0", &"192.0.2.3".parse().unwrap()));
+	
+	assert!(InterfaceAddressFilter::default().addresses().iter().all(|ipAddress| !ipAddress.is_loopback()));
+}
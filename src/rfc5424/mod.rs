@@ -2,6 +2,25 @@
 // Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
 
 
+pub use self::duplicateStructuredDataIdPolicy::DuplicateStructuredDataIdPolicy;
+mod duplicateStructuredDataIdPolicy;
+
+pub use self::duplicateStructuredDataId::DuplicateStructuredDataId;
+mod duplicateStructuredDataId;
+
+pub use self::enterpriseId::EnterpriseId;
+mod enterpriseId;
+
+pub use self::interfaceAddressFilter::InterfaceAddressFilter;
+mod interfaceAddressFilter;
+
+pub use self::origin::Origin;
+mod origin;
+
+pub use self::sequenceIdCounter::MaximumSequenceId;
+pub use self::sequenceIdCounter::SequenceIdCounter;
+mod sequenceIdCounter;
+
 pub use self::structuredData::StructuredData;
 pub use self::structuredData::write_structured_data_elements;
 mod structuredData;
@@ -12,8 +31,36 @@ mod structuredDataElement;
 pub use self::structuredDataParameter::StructuredDataParameter;
 mod structuredDataParameter;
 
+pub use self::ownedStructuredDataElement::OwnedStructuredDataElement;
+mod ownedStructuredDataElement;
+
+pub use self::ownedStructuredDataParameter::OwnedStructuredDataParameter;
+mod ownedStructuredDataParameter;
+
+pub use self::toStructuredDataElement::ToStructuredDataElement;
+mod toStructuredDataElement;
+
+pub use self::timeQuality::TimeQuality;
+mod timeQuality;
+
+#[cfg(feature = "derive")] extern crate structured_data_derive;
+#[cfg(feature = "derive")] pub use self::structured_data_derive::StructuredDataElement;
+
+pub use self::structuredDataId::StructuredDataId;
+mod structuredDataId;
+
+pub use self::structuredDataParameterName::StructuredDataParameterName;
+mod structuredDataParameterName;
+
+pub use self::structuredDataNameError::StructuredDataNameError;
+mod structuredDataNameError;
+
+pub use self::structuredDataNameValidation::IanaRegisteredStructuredDataIds;
+pub use self::structuredDataNameValidation::MaximumStructuredDataNameLength;
+pub use self::structuredDataNameValidation::is_iana_registered_structured_data_id;
+pub use self::structuredDataNameValidation::validate_structured_data_id;
+pub use self::structuredDataNameValidation::validate_structured_data_parameter_name;
+pub mod structuredDataNameValidation;
+
 pub use self::truncatedUsAsciiPrintableString::TruncatedUsAsciiPrintableString;
 pub mod truncatedUsAsciiPrintableString;
-
-use self::atomicI32::AtomicI32;
-mod atomicI32;
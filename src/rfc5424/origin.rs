@@ -0,0 +1,125 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::net::IpAddr;
+use rfc5424::EnterpriseId;
+use rfc5424::InterfaceAddressFilter;
+use rfc5424::StructuredDataElement;
+
+
+const MaximumSoftwareLength: usize = 48;
+
+const MaximumSwVersionLength: usize = 32;
+
+/// Creates an `Origin` for the calling crate, using its Cargo package name and version for `software` and `swVersion` and discovering `ip` from its network interfaces
+/// eg `origin!()`, `origin!(EnterpriseId::new(32473).subIdentifier(1))` or `origin!(EnterpriseId::new(32473), &interfaceAddressFilter)`
+#[macro_export]
+macro_rules! origin
+{
+	() =>
+	{
+		$crate::rfc5424::Origin::discover(None, env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), &$crate::rfc5424::InterfaceAddressFilter::default())
+	};
+	
+	($enterpriseId: expr) =>
+	{
+		$crate::rfc5424::Origin::discover(Some($enterpriseId), env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), &$crate::rfc5424::InterfaceAddressFilter::default())
+	};
+	
+	($enterpriseId: expr, $interfaceAddressFilter: expr) =>
+	{
+		$crate::rfc5424::Origin::discover(Some($enterpriseId), env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"), $interfaceAddressFilter)
+	};
+}
+
+/// The parameters of an `origin` element (RFC 5424 section 7.2); create once at start up, as discovering addresses is expensive
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Origin
+{
+	enterpriseId: Option<EnterpriseId>,
+	software: String,
+	swVersion: String,
+	ipAddresses: Vec<IpAddr>,
+}
+
+impl Origin
+{
+	/// `software` is truncated to 48 characters and `swVersion` to 32 characters
+	pub fn new(enterpriseId: Option<EnterpriseId>, software: &str, swVersion: &str, ipAddresses: Vec<IpAddr>) -> Origin
+	{
+		Origin
+		{
+			enterpriseId: enterpriseId,
+			software: software.chars().take(MaximumSoftwareLength).collect(),
+			swVersion: swVersion.chars().take(MaximumSwVersionLength).collect(),
+			ipAddresses: ipAddresses,
+		}
+	}
+	
+	/// As `new()`, but with `ipAddresses` enumerated from the network interfaces accepted by `interfaceAddressFilter`
+	#[inline(always)]
+	pub fn discover(enterpriseId: Option<EnterpriseId>, software: &str, swVersion: &str, interfaceAddressFilter: &InterfaceAddressFilter) -> Origin
+	{
+		Origin::new(enterpriseId, software, swVersion, interfaceAddressFilter.addresses())
+	}
+	
+	#[inline(always)]
+	pub fn enterpriseId(&self) -> Option<&EnterpriseId>
+	{
+		self.enterpriseId.as_ref()
+	}
+	
+	#[inline(always)]
+	pub fn software(&self) -> &str
+	{
+		&self.software
+	}
+	
+	#[inline(always)]
+	pub fn swVersion(&self) -> &str
+	{
+		&self.swVersion
+	}
+	
+	#[inline(always)]
+	pub fn ipAddresses(&self) -> &[IpAddr]
+	{
+		&self.ipAddresses
+	}
+	
+	#[inline(always)]
+	pub fn element<'a>(&'a self) -> StructuredDataElement<'a>
+	{
+		StructuredDataElement::origin(self.enterpriseId.as_ref(), &self.software, &self.swVersion, &self.ipAddresses)
+	}
+}
+
+#[test]
+fn originElementUsesRfcParameterNames()
+{
+	use rfc5424::StructuredData;
+	use rfc5424::write_structured_data_elements;
+	
+	let origin = Origin::new(Some(EnterpriseId::new(32473).subIdentifier(1)), "myprogram", "1.2.3", vec!["192.0.2.1".parse().unwrap()]);
+	let mut structuredData = StructuredData::new();
+	structuredData.insert(origin.element()).unwrap();
+	
+	let mut written = Vec::new();
+	write_structured_data_elements(&mut written, &structuredData);
+	assert_eq!(String::from_utf8(written).unwrap(), "[origin enterpriseId=\"32473.1\" software=\"myprogram\" swVersion=\"1.2.3\" ip=\"192.0.2.1\"]");
+}
+
+#[test]
+fn truncatesByCharactersNotBytes()
+{
+	let software: String = ::std::iter::repeat('é').take(MaximumSoftwareLength + 1).collect();
+	let swVersion: String = ::std::iter::repeat('β').take(MaximumSwVersionLength + 1).collect();
+	
+	let origin = Origin::new(None, &software, &swVersion, Vec::new());
+	assert_eq!(origin.software().chars().count(), MaximumSoftwareLength);
+	assert_eq!(origin.swVersion().chars().count(), MaximumSwVersionLength);
+	assert!(origin.software().len() > MaximumSoftwareLength);
+	
+	// Would fail the debug assertions were they to count bytes
+	assert_eq!(origin.element().parameters()[0].value(), origin.software());
+}
@@ -0,0 +1,95 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+
+use rfc5424::OwnedStructuredDataParameter;
+use rfc5424::StructuredDataElement;
+use rfc5424::StructuredDataId;
+use rfc5424::StructuredDataNameError;
+
+
+/// An element that owns its SD-ID and parameters, built fluently from runtime data, eg
+/// `OwnedStructuredDataElement::new("req@38188").param("id", requestId).param("path", path)`
+/// Use `borrow()` to insert it into `StructuredData`; it renders exactly as the equivalent `StructuredDataElement`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OwnedStructuredDataElement
+{
+	id: StructuredDataId,
+	parameters: Vec<OwnedStructuredDataParameter>,
+}
+
+impl OwnedStructuredDataElement
+{
+	/// Panics if `id` is not a valid SD-ID; use `try_new()` for ids from elsewhere
+	#[inline(always)]
+	pub fn new(id: &str) -> OwnedStructuredDataElement
+	{
+		match OwnedStructuredDataElement::try_new(id)
+		{
+			Ok(element) => element,
+			Err(error) => panic!("Invalid SD-ID: {}", error),
+		}
+	}
+	
+	pub fn try_new(id: &str) -> Result<OwnedStructuredDataElement, StructuredDataNameError>
+	{
+		Ok(OwnedStructuredDataElement
+		{
+			id: try!(StructuredDataId::new(id)),
+			parameters: Vec::new(),
+		})
+	}
+	
+	/// Panics if `name` is not a valid PARAM-NAME; use `try_param()` for names from elsewhere
+	#[inline(always)]
+	pub fn param<V: ToString>(self, name: &str, value: V) -> OwnedStructuredDataElement
+	{
+		match self.try_param(name, value)
+		{
+			Ok(element) => element,
+			Err(error) => panic!("Invalid PARAM-NAME: {}", error),
+		}
+	}
+	
+	pub fn try_param<V: ToString>(mut self, name: &str, value: V) -> Result<OwnedStructuredDataElement, StructuredDataNameError>
+	{
+		self.parameters.push(try!(OwnedStructuredDataParameter::new(name, value.to_string())));
+		Ok(self)
+	}
+	
+	#[inline(always)]
+	pub fn id(&self) -> &StructuredDataId
+	{
+		&self.id
+	}
+	
+	#[inline(always)]
+	pub fn parameters(&self) -> &[OwnedStructuredDataParameter]
+	{
+		&self.parameters
+	}
+	
+	pub fn borrow(&self) -> StructuredDataElement
+	{
+		StructuredDataElement::new(&self.id, self.parameters.iter().map(|parameter| parameter.borrow()).collect())
+	}
+	
+	#[inline(always)]
+	pub fn write(&self, writer: &mut Vec<u8>)
+	{
+		self.borrow().write(writer)
+	}
+}
+
+#[test]
+fn rendersAsTheEquivalentBorrowedElement()
+{
+	let requestId = 42;
+	let element = OwnedStructuredDataElement::new("req@38188").param("id", requestId).param("path", "/a]b");
+	
+	let mut rendered = Vec::new();
+	element.write(&mut rendered);
+	assert_eq!(String::from_utf8(rendered).unwrap(), "[req@38188 id=\"42\" path=\"/a\\]b\"]");
+	
+	assert_eq!(OwnedStructuredDataElement::new("req@38188").try_param("a=b", 1), Err(StructuredDataNameError::InvalidCharacter { character: '=', index: 1 }));
+}
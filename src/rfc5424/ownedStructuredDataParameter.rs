@@ -0,0 +1,47 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+
+use std::borrow::Cow;
+use rfc5424::StructuredDataParameter;
+use rfc5424::StructuredDataParameterName;
+use rfc5424::StructuredDataNameError;
+
+
+/// A parameter that owns its name and value, for names known only at runtime
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OwnedStructuredDataParameter
+{
+	name: StructuredDataParameterName,
+	value: String,
+}
+
+impl OwnedStructuredDataParameter
+{
+	pub fn new(name: &str, value: String) -> Result<OwnedStructuredDataParameter, StructuredDataNameError>
+	{
+		Ok(OwnedStructuredDataParameter
+		{
+			name: try!(StructuredDataParameterName::new(name)),
+			value: value,
+		})
+	}
+	
+	#[inline(always)]
+	pub fn name(&self) -> &StructuredDataParameterName
+	{
+		&self.name
+	}
+	
+	#[inline(always)]
+	pub fn value(&self) -> &str
+	{
+		&self.value
+	}
+	
+	#[inline(always)]
+	pub fn borrow(&self) -> StructuredDataParameter
+	{
+		self.name.parameter(Cow::Borrowed(&self.value))
+	}
+}
@@ -0,0 +1,91 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use fork_generation;
+
+
+/// The largest `sequenceId` permitted by RFC 5424 section 7.3.1; the next is 1
+pub const MaximumSequenceId: u32 = 2147483647;
+
+/// Produces the `sequenceId` parameter of a `meta` element for one stream of messages, starting at 1
+/// A child process restarts at 1 after `fork()`, as it is a new stream
+#[derive(Debug)]
+pub struct SequenceIdCounter
+{
+	next: AtomicUsize,
+	forkGeneration: AtomicUsize,
+}
+
+impl Default for SequenceIdCounter
+{
+	#[inline(always)]
+	fn default() -> SequenceIdCounter
+	{
+		SequenceIdCounter::new()
+	}
+}
+
+impl SequenceIdCounter
+{
+	#[inline(always)]
+	pub fn new() -> SequenceIdCounter
+	{
+		SequenceIdCounter::startingAt(1)
+	}
+	
+	/// `sequenceId` must be between 1 and `MaximumSequenceId` inclusive
+	pub fn startingAt(sequenceId: u32) -> SequenceIdCounter
+	{
+		debug_assert!(sequenceId >= 1 && sequenceId <= MaximumSequenceId, "sequenceId must be between 1 and {}, not {}", MaximumSequenceId, sequenceId);
+		
+		SequenceIdCounter
+		{
+			next: AtomicUsize::new(sequenceId as usize),
+			forkGeneration: AtomicUsize::new(fork_generation()),
+		}
+	}
+	
+	pub fn next(&self) -> u32
+	{
+		let forkGeneration = fork_generation();
+		if self.forkGeneration.swap(forkGeneration, Ordering::Relaxed) != forkGeneration
+		{
+			self.next.store(1, Ordering::Relaxed);
+		}
+		
+		let mut current = self.next.load(Ordering::Relaxed);
+		loop
+		{
+			let next = if current >= MaximumSequenceId as usize
+			{
+				1
+			}
+			else
+			{
+				current + 1
+			};
+			
+			match self.next.compare_exchange_weak(current, next, Ordering::Relaxed, Ordering::Relaxed)
+			{
+				Ok(_) => return current as u32,
+				Err(previous) => current = previous,
+			}
+		}
+	}
+}
+
+#[test]
+fn wrapsFromMaximumToOne()
+{
+	let sequenceIdCounter = SequenceIdCounter::new();
+	assert_eq!(sequenceIdCounter.next(), 1);
+	assert_eq!(sequenceIdCounter.next(), 2);
+	
+	let sequenceIdCounter = SequenceIdCounter::startingAt(MaximumSequenceId - 1);
+	assert_eq!(sequenceIdCounter.next(), 2147483646);
+	assert_eq!(sequenceIdCounter.next(), 2147483647);
+	assert_eq!(sequenceIdCounter.next(), 1);
+	assert_eq!(sequenceIdCounter.next(), 2);
+}
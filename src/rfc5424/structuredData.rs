@@ -2,11 +2,143 @@
 // Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
 
 
-use std::collections::HashSet;
+use std::collections::HashMap;
+use std::slice::Iter;
+use rfc5424::DuplicateStructuredDataId;
+use rfc5424::DuplicateStructuredDataIdPolicy;
 use rfc5424::StructuredDataElement;
 
 
-pub type StructuredData<'a> = HashSet<StructuredDataElement<'a>>;
+/// Elements in insertion order, so output is deterministic (for golden tests and signatures), with lookup by SD-ID
+/// Duplicate SD-IDs are handled according to a `DuplicateStructuredDataIdPolicy`
+#[derive(Debug, Clone, Default)]
+pub struct StructuredData<'a>
+{
+	elements: Vec<StructuredDataElement<'a>>,
+	indices: HashMap<&'a str, usize>,
+	duplicatePolicy: DuplicateStructuredDataIdPolicy,
+}
+
+impl <'a> StructuredData<'a>
+{
+	/// Uses `DuplicateStructuredDataIdPolicy::Reject`
+	#[inline(always)]
+	pub fn new() -> StructuredData<'a>
+	{
+		StructuredData::default()
+	}
+	
+	pub fn withDuplicatePolicy(duplicatePolicy: DuplicateStructuredDataIdPolicy) -> StructuredData<'a>
+	{
+		StructuredData
+		{
+			elements: Vec::new(),
+			indices: HashMap::new(),
+			duplicatePolicy: duplicatePolicy,
+		}
+	}
+	
+	#[inline(always)]
+	pub fn duplicatePolicy(&self) -> DuplicateStructuredDataIdPolicy
+	{
+		self.duplicatePolicy
+	}
+	
+	#[inline(always)]
+	pub fn len(&self) -> usize
+	{
+		self.elements.len()
+	}
+	
+	#[inline(always)]
+	pub fn is_empty(&self) -> bool
+	{
+		self.elements.is_empty()
+	}
+	
+	#[inline(always)]
+	pub fn iter(&self) -> Iter<StructuredDataElement<'a>>
+	{
+		self.elements.iter()
+	}
+	
+	#[inline(always)]
+	pub fn contains(&self, id: &str) -> bool
+	{
+		self.indices.contains_key(id)
+	}
+	
+	#[inline(always)]
+	pub fn get(&self, id: &str) -> Option<&StructuredDataElement<'a>>
+	{
+		self.indices.get(id).map(|&index| &self.elements[index])
+	}
+	
+	/// Returns true if `structured_data_element` was inserted, replaced an existing element or was merged into one, and false if it was discarded under `DuplicateStructuredDataIdPolicy::KeepFirst`
+	/// An error if the policy is `DuplicateStructuredDataIdPolicy::Reject` and an element with the same SD-ID is present
+	pub fn insert(&mut self, structured_data_element: StructuredDataElement<'a>) -> Result<bool, DuplicateStructuredDataId>
+	{
+		let id = structured_data_element.id().as_str();
+		let index = match self.indices.get(id).cloned()
+		{
+			None =>
+			{
+				self.indices.insert(id, self.elements.len());
+				self.elements.push(structured_data_element);
+				return Ok(true);
+			}
+			Some(index) => index,
+		};
+		
+		match self.duplicatePolicy
+		{
+			DuplicateStructuredDataIdPolicy::Reject => Err(DuplicateStructuredDataId(id.to_owned())),
+			DuplicateStructuredDataIdPolicy::KeepFirst => Ok(false),
+			DuplicateStructuredDataIdPolicy::Replace =>
+			{
+				self.elements[index] = structured_data_element;
+				Ok(true)
+			},
+			DuplicateStructuredDataIdPolicy::Merge =>
+			{
+				self.elements[index].extend_parameters(structured_data_element);
+				Ok(true)
+			},
+		}
+	}
+	
+	/// Preserves the order of the remaining elements
+	pub fn remove(&mut self, id: &str) -> Option<StructuredDataElement<'a>>
+	{
+		let index = match self.indices.remove(id)
+		{
+			None => return None,
+			Some(index) => index,
+		};
+		
+		let removed = self.elements.remove(index);
+		for laterIndex in self.indices.values_mut()
+		{
+			if *laterIndex > index
+			{
+				*laterIndex -= 1;
+			}
+		}
+		Some(removed)
+	}
+}
+
+impl <'a, 'b> IntoIterator for &'b StructuredData<'a>
+{
+	type Item = &'b StructuredDataElement<'a>;
+	type IntoIter = Iter<'b, StructuredDataElement<'a>>;
+	
+	#[inline(always)]
+	fn into_iter(self) -> Self::IntoIter
+	{
+		self.elements.iter()
+	}
+}
 
 pub fn write_structured_data_elements<'a>(mut writer: &mut Vec<u8>, structured_data_elements: &StructuredData)
 {
@@ -21,3 +153,41 @@ pub fn write_structured_data_elements<'a>(mut writer: &mut Vec<u8>, structured_d
 		structured_data_element.write(&mut writer);
 	}
 }
+
+#[test]
+fn keepsInsertionOrderAndAppliesDuplicatePolicy()
+{
+	use std::borrow::Cow;
+	use rfc5424::TruncatedUsAsciiPrintableString;
+	
+	let first = TruncatedUsAsciiPrintableString::new_sd_name("first@38188");
+	let second = TruncatedUsAsciiPrintableString::new_sd_name("second@38188");
+	let name = TruncatedUsAsciiPrintableString::new_sd_name("name");
+	let element = |id, value: &'static str| StructuredDataElement::new(id, vec![name.parameter(Cow::Borrowed(value))]);
+	
+	let mut structuredData = StructuredData::new();
+	assert_eq!(structuredData.insert(element(&second, "a")), Ok(true));
+	assert_eq!(structuredData.insert(element(&first, "b")), Ok(true));
+	assert_eq!(structuredData.insert(element(&second, "c")), Err(DuplicateStructuredDataId("second@38188".to_owned())));
+	let mut rendered = Vec::new();
+	write_structured_data_elements(&mut rendered, &structuredData);
+	assert_eq!(String::from_utf8(rendered).unwrap(), "[second@38188 name=\"a\"][first@38188 name=\"b\"]");
+	
+	let mut structuredData = StructuredData::withDuplicatePolicy(DuplicateStructuredDataIdPolicy::KeepFirst);
+	assert_eq!(structuredData.insert(element(&first, "a")), Ok(true));
+	assert_eq!(structuredData.insert(element(&first, "b")), Ok(false));
+	assert_eq!(structuredData.get("first@38188").unwrap().parameters()[0].value(), "a");
+	
+	let mut structuredData = StructuredData::withDuplicatePolicy(DuplicateStructuredDataIdPolicy::Merge);
+	assert_eq!(structuredData.insert(element(&first, "a")), Ok(true));
+	assert_eq!(structuredData.insert(element(&first, "b")), Ok(true));
+	assert_eq!(structuredData.get("first@38188").unwrap().parameters().len(), 2);
+	
+	let mut structuredData = StructuredData::withDuplicatePolicy(DuplicateStructuredDataIdPolicy::Replace);
+	structuredData.insert(element(&first, "a")).unwrap();
+	structuredData.insert(element(&second, "b")).unwrap();
+	assert_eq!(structuredData.insert(element(&first, "c")), Ok(true));
+	assert_eq!(structuredData.iter().next().unwrap().parameters()[0].value(), "c");
+	assert!(structuredData.remove("first@38188").is_some());
+	assert_eq!(structuredData.get("second@38188").unwrap().parameters()[0].value(), "b");
+}
@@ -2,21 +2,15 @@
 // Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
 
 
-extern crate uptime;
-use self::uptime::uptime_in_microseconds;
 use std::net::IpAddr;
 use std::cmp::PartialEq;
 use std::cmp::Eq;
 use std::hash::Hash;
 use std::hash::Hasher;
-use std::sync::atomic::Ordering;
-use std::cmp::max;
+use rfc5424::EnterpriseId;
 use rfc5424::TruncatedUsAsciiPrintableString;
 use rfc5424::StructuredDataParameter;
 use rfc5424::truncatedUsAsciiPrintableString::WriteTruncatedUsAsciiPrintableString;
-use rfc5424::atomicI32::AtomicI32;
-
-static mut SequenceId: AtomicI32 = AtomicI32::new(1);
 
 lazy_static!
 {
@@ -27,14 +21,24 @@ lazy_static!
 	pub static ref originSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("origin");
 	pub static ref enterpriseIdSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("enterpriseId");
 	pub static ref softwareSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("software");
-	pub static ref softwareVersionSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("softwareVersion");
+	pub static ref swVersionSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("swVersion");
 	pub static ref ipSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("ip");
 	pub static ref metaSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("meta");
 	pub static ref sequenceIdSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("sequenceId");
-	pub static ref sysUpTime: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("sysUpTime");
+	pub static ref sysUpTimeSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("sysUpTime");
 	pub static ref languageSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("language");
+	pub static ref samplingSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("sampling@38188");
+	pub static ref rateSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("rate");
+	pub static ref sourceSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("source@38188");
+	pub static ref modulePathSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("modulePath");
+	pub static ref fileSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("file");
+	pub static ref lineSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("line");
+	pub static ref panicSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("panic@38188");
+	pub static ref threadSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("thread");
+	pub static ref backtraceSdName: TruncatedUsAsciiPrintableString = TruncatedUsAsciiPrintableString::new_sd_name("backtrace");
 }
 
+#[derive(Debug, Clone)]
 pub struct StructuredDataElement<'a>
 {
 	id: &'a TruncatedUsAsciiPrintableString,
@@ -45,6 +49,34 @@ impl <'a> StructuredDataElement<'a>
 {
 	// More at https://www.iana.org/assignments/syslog2-parameters/syslog2-parameters.xhtml
 	
+	pub fn new(id: &'a TruncatedUsAsciiPrintableString, parameters: Vec<StructuredDataParameter<'a>>) -> StructuredDataElement<'a>
+	{
+		StructuredDataElement
+		{
+			id: id,
+			parameters: parameters,
+		}
+	}
+	
+	#[inline(always)]
+	pub fn id(&self) -> &'a TruncatedUsAsciiPrintableString
+	{
+		self.id
+	}
+	
+	#[inline(always)]
+	pub fn parameters(&self) -> &[StructuredDataParameter<'a>]
+	{
+		&self.parameters
+	}
+	
+	/// Appends `other`'s parameters, regardless of its id
+	#[inline(always)]
+	pub fn extend_parameters(&mut self, other: StructuredDataElement<'a>)
+	{
+		self.parameters.extend(other.parameters);
+	}
+	
 	pub fn write(&self, mut writer: &mut Vec<u8>)
 	{	
 		writer.push(b'[');
@@ -59,10 +91,11 @@ impl <'a> StructuredDataElement<'a>
 		writer.push(b']');
 	}
 	
-	/// syncAccuracy must be 0 if isSynced is false
-	pub fn timeQuality(tzKnown: bool, isSynced: bool, syncAccuracy: u64) -> StructuredDataElement<'a>
+	/// syncAccuracy is in microseconds, and must be None if isSynced is false; it is omitted if None
+	/// Use `TimeQuality::current().element()` to have these values taken from the kernel clock state
+	pub fn timeQuality(tzKnown: bool, isSynced: bool, syncAccuracy: Option<u64>) -> StructuredDataElement<'a>
 	{
-		debug_assert!(isSynced && syncAccuracy != 0, "syncAccuracy must be 0 (not {}) if isSynced is false", syncAccuracy);
+		debug_assert!(isSynced || syncAccuracy.is_none(), "syncAccuracy must be None (not {:?}) if isSynced is false", syncAccuracy);
 		
 		let tzKnownValue = match tzKnown
 		{
@@ -76,28 +109,38 @@ impl <'a> StructuredDataElement<'a>
 			false => "0"
 		};
 		
-		let syncAccuracyValue = syncAccuracy.to_string();
+		let mut parameters: Vec<StructuredDataParameter<'a>> = Vec::with_capacity(3);
+		parameters.push(tzKnownSdName.parameter(tzKnownValue.into()));
+		parameters.push(isSyncedSdName.parameter(isSyncedValue.into()));
+		if isSynced
+		{
+			if let Some(syncAccuracy) = syncAccuracy
+			{
+				parameters.push(syncAccuracySdName.parameter(syncAccuracy.to_string().into()));
+			}
+		}
 		
 		StructuredDataElement
 		{
 			id: &timeQualitySdName,
-			parameters: vec![tzKnownSdName.parameter(tzKnownValue.into()), isSyncedSdName.parameter(isSyncedValue.into()), syncAccuracySdName.parameter(syncAccuracyValue.into())]
+			parameters: parameters,
 		}
 	}
 	
-	// /// software must be less than 48 characters
-	// /// softwareVersion must be less than 32 characters
-	// /// StormMQ's ianaPrivateEnterpriseNumber is 38188, for instance
-	// /// We do not support sub-identifiers (we could, using a vec!, I suppose)
-	pub fn origin(ianaPrivateEnterpriseNumber: u32, software: &'a str, softwareVersion: &'a str, ipAddresses: &Vec<IpAddr>) -> StructuredDataElement<'a>
+	/// software must be no more than 48 characters and swVersion no more than 32 characters
+	/// Use `Origin` (or the `origin!()` macro) to have ipAddresses discovered from network interfaces
+	pub fn origin(enterpriseId: Option<&EnterpriseId>, software: &'a str, swVersion: &'a str, ipAddresses: &[IpAddr]) -> StructuredDataElement<'a>
 	{
-		debug_assert!(software.len() <= 48, "software must be less than 48 characters, not {}", software.len());
-		debug_assert!(softwareVersion.len() <= 32, "softwareVersion must be less than 32 characters, not {}", softwareVersion.len());
+		debug_assert!(software.chars().count() <= 48, "software must be no more than 48 characters, not {}", software.chars().count());
+		debug_assert!(swVersion.chars().count() <= 32, "swVersion must be no more than 32 characters, not {}", swVersion.chars().count());
 
 		let mut parameters: Vec<StructuredDataParameter<'a>> = Vec::with_capacity(3 + ipAddresses.len());
-		parameters.push(enterpriseIdSdName.parameter(format!("1.3.6.1.4.1.{}", ianaPrivateEnterpriseNumber).into()));
+		if let Some(enterpriseId) = enterpriseId
+		{
+			parameters.push(enterpriseIdSdName.parameter(enterpriseId.to_string().into()));
+		}
 		parameters.push(softwareSdName.parameter(software.into()));
-		parameters.push(softwareVersionSdName.parameter(softwareVersion.into()));
+		parameters.push(swVersionSdName.parameter(swVersion.into()));
 		for ipAddress in ipAddresses
 		{
 			parameters.push(ipSdName.parameter(ipAddress.to_string().into()));
@@ -110,39 +153,85 @@ impl <'a> StructuredDataElement<'a>
 		}
 	}
 
-	pub fn meta() -> StructuredDataElement<'a>
+	/// A private element (using StormMQ's private enterprise number, 38188) recording that a message was sampled
+	/// `rate` is the number of messages this one stands for, eg 10 if 1 in 10 were kept, so that downstream tools can re-weight counts
+	pub fn sampling(rate: String) -> StructuredDataElement<'a>
+	{
+		StructuredDataElement
+		{
+			id: &samplingSdName,
+			parameters: vec![rateSdName.parameter(rate.into())],
+		}
+	}
+	
+	/// A private element (using StormMQ's private enterprise number, 38188) recording where in the source code a message was logged
+	/// Parameters which are None are omitted
+	pub fn source(modulePath: Option<&'a str>, file: Option<&'a str>, line: Option<u32>) -> StructuredDataElement<'a>
 	{
 		let mut parameters: Vec<StructuredDataParameter<'a>> = Vec::with_capacity(3);
+		if let Some(modulePath) = modulePath
+		{
+			parameters.push(modulePathSdName.parameter(modulePath.into()));
+		}
+		if let Some(file) = file
+		{
+			parameters.push(fileSdName.parameter(file.into()));
+		}
+		if let Some(line) = line
+		{
+			parameters.push(lineSdName.parameter(line.to_string().into()));
+		}
 		
-		let mut current: i32;
-		current = unsafe { SequenceId.load(Ordering::Relaxed) };
-		loop
+		StructuredDataElement
 		{
-			let next = if current == 2147483647
-			{
-				1
-			}
-			else
-			{
-				current + 1
-			};
-			let previous = unsafe { SequenceId.compare_and_swap(current, next, Ordering::Relaxed) };
-			if previous == current
-			{
-				break
-			}
-			current = previous;
+			id: &sourceSdName,
+			parameters: parameters,
+		}
+	}
+	
+	/// A private element (using StormMQ's private enterprise number, 38188) describing a panic
+	pub fn panic(thread: &'a str, file: Option<&'a str>, line: Option<u32>, backtrace: Option<String>) -> StructuredDataElement<'a>
+	{
+		let mut parameters: Vec<StructuredDataParameter<'a>> = Vec::with_capacity(4);
+		parameters.push(threadSdName.parameter(thread.into()));
+		if let Some(file) = file
+		{
+			parameters.push(fileSdName.parameter(file.into()));
+		}
+		if let Some(line) = line
+		{
+			parameters.push(lineSdName.parameter(line.to_string().into()));
+		}
+		if let Some(backtrace) = backtrace
+		{
+			parameters.push(backtraceSdName.parameter(backtrace.into()));
 		}
-		parameters.push(sequenceIdSdName.parameter(current.to_string().into()));
 		
-		if let Ok(uptime_in_microseconds) = uptime_in_microseconds()
+		StructuredDataElement
+		{
+			id: &panicSdName,
+			parameters: parameters,
+		}
+	}
+	
+	/// sequenceId should come from a `SequenceIdCounter`, sysUpTime is in hundredths of a second and language is a language tag (RFC 4646); parameters which are None are omitted
+	/// Use a `MetaSyslogSender` to have these attached to every message
+	pub fn meta(sequenceId: Option<u32>, sysUpTime: Option<u64>, language: Option<&'a str>) -> StructuredDataElement<'a>
+	{
+		let mut parameters: Vec<StructuredDataParameter<'a>> = Vec::with_capacity(3);
+		if let Some(sequenceId) = sequenceId
+		{
+			parameters.push(sequenceIdSdName.parameter(sequenceId.to_string().into()));
+		}
+		if let Some(sysUpTime) = sysUpTime
+		{
+			parameters.push(sysUpTimeSdName.parameter(sysUpTime.to_string().into()));
+		}
+		if let Some(language) = language
 		{
-			let uptime_in_hundredths_of_a_second = max(uptime_in_microseconds, 0) / 10_000;
-			parameters.push(sysUpTime.parameter(uptime_in_hundredths_of_a_second.to_string().into()));
+			parameters.push(languageSdName.parameter(language.into()));
 		}
 		
-		parameters.push(languageSdName.parameter("en".into()));
-				
 		StructuredDataElement
 		{
 			id: &metaSdName,
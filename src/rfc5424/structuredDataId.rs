@@ -0,0 +1,80 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::ops::Deref;
+use rfc5424::TruncatedUsAsciiPrintableString;
+use rfc5424::StructuredDataNameError;
+use rfc5424::structuredDataNameValidation::is_iana_registered_structured_data_id;
+use rfc5424::structuredDataNameValidation::validate_structured_data_id;
+
+
+/// A validated SD-ID; either IANA-registered (eg `timeQuality`) or private (eg `sampling@38188`)
+/// Dereferences to a `TruncatedUsAsciiPrintableString`, so can be used with `StructuredDataElement::new()`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StructuredDataId(TruncatedUsAsciiPrintableString);
+
+impl StructuredDataId
+{
+	pub fn new(id: &str) -> Result<StructuredDataId, StructuredDataNameError>
+	{
+		try!(validate_structured_data_id(id));
+		Ok(StructuredDataId(TruncatedUsAsciiPrintableString::new(id, 32)))
+	}
+	
+	#[inline(always)]
+	pub fn is_iana_registered(&self) -> bool
+	{
+		is_iana_registered_structured_data_id(self.0.as_str())
+	}
+	
+	/// The part before the `@`, or the whole of an IANA-registered SD-ID
+	#[inline(always)]
+	pub fn name(&self) -> &str
+	{
+		self.0.as_str().splitn(2, '@').next().unwrap()
+	}
+	
+	/// eg `32473.1` for `example@32473.1`; None if IANA-registered
+	#[inline(always)]
+	pub fn enterpriseNumber(&self) -> Option<&str>
+	{
+		self.0.as_str().splitn(2, '@').nth(1)
+	}
+}
+
+impl Deref for StructuredDataId
+{
+	type Target = TruncatedUsAsciiPrintableString;
+	
+	#[inline(always)]
+	fn deref(&self) -> &TruncatedUsAsciiPrintableString
+	{
+		&self.0
+	}
+}
+
+impl Display for StructuredDataId
+{
+	#[inline(always)]
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result
+	{
+		formatter.write_str(self.0.as_str())
+	}
+}
+
+#[test]
+fn validatesRegisteredAndPrivateStructuredDataIds()
+{
+	assert!(StructuredDataId::new("timeQuality").unwrap().is_iana_registered());
+	assert_eq!(StructuredDataId::new("example@32473.1").unwrap().enterpriseNumber(), Some("32473.1"));
+	assert_eq!(StructuredDataId::new("example"), Err(StructuredDataNameError::NotRegisteredWithIana("example".to_owned())));
+	assert_eq!(StructuredDataId::new("@32473"), Err(StructuredDataNameError::EmptyNameBeforeAt));
+	assert_eq!(StructuredDataId::new("a@b@32473"), Err(StructuredDataNameError::MoreThanOneAt));
+	assert_eq!(StructuredDataId::new("example@32473."), Err(StructuredDataNameError::InvalidEnterpriseNumber("32473.".to_owned())));
+	assert_eq!(StructuredDataId::new("exa mple@32473"), Err(StructuredDataNameError::InvalidCharacter { character: ' ', index: 3 }));
+	assert_eq!(StructuredDataId::new("averyveryveryverylongname@3247312"), Err(StructuredDataNameError::TooLong(33)));
+}
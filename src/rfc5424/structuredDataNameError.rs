@@ -0,0 +1,60 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+// This file must only depend on `std` and structuredDataNameValidation.rs, as both are also included, using `#[path]`, by crates which can not depend on this one
+
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use super::structuredDataNameValidation::MaximumStructuredDataNameLength;
+
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum StructuredDataNameError
+{
+	Empty,
+	
+	/// Length in bytes; the maximum is 32
+	TooLong(usize),
+	
+	/// Control codes, space, DEL, `"`, `=`, `]` and anything not US-ASCII are not permitted
+	InvalidCharacter
+	{
+		character: char,
+		index: usize,
+	},
+	
+	/// RFC 5424 section 6.3.2: names without an `@` are reserved for IANA registration
+	NotRegisteredWithIana(String),
+	
+	/// Nothing before the `@` in `name@enterpriseNumber`
+	EmptyNameBeforeAt,
+	
+	MoreThanOneAt,
+	
+	/// After the `@`, a private enterprise number such as `32473`, optionally followed by dotted sub-identifiers such as `32473.1.2`, is required
+	InvalidEnterpriseNumber(String),
+}
+
+impl Display for StructuredDataNameError
+{
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result
+	{
+		match *self
+		{
+			StructuredDataNameError::Empty => write!(formatter, "name is empty"),
+			StructuredDataNameError::TooLong(length) => write!(formatter, "name is {} bytes long, but the maximum is {}", length, MaximumStructuredDataNameLength),
+			StructuredDataNameError::InvalidCharacter { character, index } => write!(formatter, "name contains the invalid character {:?} at {}", character, index),
+			StructuredDataNameError::NotRegisteredWithIana(ref name) => write!(formatter, "'{}' is not an IANA-registered SD-ID; use the form 'name@enterpriseNumber'", name),
+			StructuredDataNameError::EmptyNameBeforeAt => write!(formatter, "name before '@' is empty"),
+			StructuredDataNameError::MoreThanOneAt => write!(formatter, "name contains more than one '@'"),
+			StructuredDataNameError::InvalidEnterpriseNumber(ref enterpriseNumber) => write!(formatter, "'{}' is not a valid private enterprise number", enterpriseNumber),
+		}
+	}
+}
+
+impl Error for StructuredDataNameError
+{
+}
@@ -0,0 +1,106 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+// This file must only depend on `std` and structuredDataNameError.rs, as both are also included, using `#[path]`, by crates which can not depend on this one
+
+
+use super::structuredDataNameError::StructuredDataNameError;
+
+
+pub const MaximumStructuredDataNameLength: usize = 32;
+
+/// SD-IDs registered with IANA; see https://www.iana.org/assignments/syslog-parameters/syslog-parameters.xhtml
+pub const IanaRegisteredStructuredDataIds: [&'static str; 7] =
+[
+	"timeQuality",
+	"origin",
+	"meta",
+	"snmp",
+	"alarm",
+	"ssign",
+	"ssign-cert",
+];
+
+/// Printable US-ASCII except space, double quote, equals and closing square bracket
+/// Backslash is also excluded, as some receivers mis-handle it in names
+#[inline(always)]
+pub fn is_valid_sd_name_character(character: char) -> bool
+{
+	match character
+	{
+		'"' | '=' | '\\' | ']' => false,
+		'\x21' ... '\x7E' => true,
+		_ => false,
+	}
+}
+
+#[inline(always)]
+pub fn is_iana_registered_structured_data_id(id: &str) -> bool
+{
+	IanaRegisteredStructuredDataIds.iter().any(|registered| *registered == id)
+}
+
+/// A PARAM-NAME (or the part of an SD-ID before any `@`)
+pub fn validate_structured_data_parameter_name(name: &str) -> Result<(), StructuredDataNameError>
+{
+	if name.is_empty()
+	{
+		return Err(StructuredDataNameError::Empty);
+	}
+	
+	if name.len() > MaximumStructuredDataNameLength
+	{
+		return Err(StructuredDataNameError::TooLong(name.len()));
+	}
+	
+	match name.char_indices().find(|&(_, character)| !is_valid_sd_name_character(character))
+	{
+		None => Ok(()),
+		Some((index, character)) => Err(StructuredDataNameError::InvalidCharacter
+		{
+			character: character,
+			index: index,
+		}),
+	}
+}
+
+/// Either an IANA-registered SD-ID, such as `timeQuality`, or a private one of the form `name@enterpriseNumber`, such as `sampling@38188` or `example@32473.1`
+pub fn validate_structured_data_id(id: &str) -> Result<(), StructuredDataNameError>
+{
+	try!(validate_structured_data_parameter_name(id));
+	
+	let mut parts = id.splitn(2, '@');
+	let name = parts.next().unwrap();
+	let enterpriseNumber = match parts.next()
+	{
+		None => return if is_iana_registered_structured_data_id(id)
+		{
+			Ok(())
+		}
+		else
+		{
+			Err(StructuredDataNameError::NotRegisteredWithIana(id.to_owned()))
+		},
+		Some(enterpriseNumber) => enterpriseNumber,
+	};
+	
+	if name.is_empty()
+	{
+		return Err(StructuredDataNameError::EmptyNameBeforeAt);
+	}
+	
+	if enterpriseNumber.contains('@')
+	{
+		return Err(StructuredDataNameError::MoreThanOneAt);
+	}
+	
+	let isValidEnterpriseNumber = enterpriseNumber.split('.').all(|subIdentifier| !subIdentifier.is_empty() && subIdentifier.bytes().all(|byte| byte >= b'0' && byte <= b'9'));
+	if isValidEnterpriseNumber
+	{
+		Ok(())
+	}
+	else
+	{
+		Err(StructuredDataNameError::InvalidEnterpriseNumber(enterpriseNumber.to_owned()))
+	}
+}
@@ -9,6 +9,7 @@ use std::io::Write;
 use VecU8PushStr;
 
 
+#[derive(Debug, Clone)]
 pub struct StructuredDataParameter<'a>
 {
 	name: &'a TruncatedUsAsciiPrintableString,
@@ -26,6 +27,18 @@ impl <'a> StructuredDataParameter<'a>
 		}
 	}
 	
+	#[inline(always)]
+	pub fn name(&self) -> &'a TruncatedUsAsciiPrintableString
+	{
+		self.name
+	}
+	
+	#[inline(always)]
+	pub fn value(&self) -> &str
+	{
+		&self.value
+	}
+	
 	pub fn write(&self, mut writer: &mut Vec<u8>)
 	{
 		writer.push(b' ');
@@ -37,7 +50,7 @@ impl <'a> StructuredDataParameter<'a>
 			{
 				'"' => writer.push_str("\\\""),
 				'\\' => writer.push_str("\\\\"),
-				']' => writer.push_str("\\["),
+				']' => writer.push_str("\\]"),
 				_ => 
 				{
 					// TODO: This is deeply sub-optimal, but encode_utf8() is not yet stable and we can't use it.
@@ -50,3 +63,12 @@ impl <'a> StructuredDataParameter<'a>
 		writer.push(b'"');
 	}
 }
+
+#[test]
+fn escapesQuotesBackslashesAndClosingSquareBrackets()
+{
+	let name = TruncatedUsAsciiPrintableString::new_sd_name("value");
+	let mut writer = Vec::new();
+	StructuredDataParameter::new(&name, Cow::Borrowed("a]b\"c\\d[e")).write(&mut writer);
+	assert_eq!(String::from_utf8(writer).unwrap(), " value=\"a\\]b\\\"c\\\\d[e\"");
+}
@@ -0,0 +1,68 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+
+use std::borrow::Cow;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::ops::Deref;
+use rfc5424::StructuredDataParameter;
+use rfc5424::TruncatedUsAsciiPrintableString;
+use rfc5424::StructuredDataNameError;
+use rfc5424::structuredDataNameValidation::validate_structured_data_parameter_name;
+
+
+/// A validated PARAM-NAME
+/// Dereferences to a `TruncatedUsAsciiPrintableString`, so can be used with `StructuredDataParameter::new()`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StructuredDataParameterName(TruncatedUsAsciiPrintableString);
+
+impl StructuredDataParameterName
+{
+	pub fn new(name: &str) -> Result<StructuredDataParameterName, StructuredDataNameError>
+	{
+		try!(validate_structured_data_parameter_name(name));
+		Ok(StructuredDataParameterName(TruncatedUsAsciiPrintableString::new(name, 32)))
+	}
+	
+	#[inline(always)]
+	pub fn parameter<'a>(&'a self, value: Cow<'a, str>) -> StructuredDataParameter<'a>
+	{
+		self.0.parameter(value)
+	}
+}
+
+impl Deref for StructuredDataParameterName
+{
+	type Target = TruncatedUsAsciiPrintableString;
+	
+	#[inline(always)]
+	fn deref(&self) -> &TruncatedUsAsciiPrintableString
+	{
+		&self.0
+	}
+}
+
+impl Display for StructuredDataParameterName
+{
+	#[inline(always)]
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result
+	{
+		formatter.write_str(self.0.as_str())
+	}
+}
+
+#[test]
+fn validatesParameterNames()
+{
+	assert_eq!(StructuredDataParameterName::new("a@b").unwrap().to_string(), "a@b");
+	assert_eq!(StructuredDataParameterName::new(""), Err(StructuredDataNameError::Empty));
+	assert!(StructuredDataParameterName::new(&"a".repeat(32)).is_ok());
+	assert_eq!(StructuredDataParameterName::new(&"a".repeat(33)), Err(StructuredDataNameError::TooLong(33)));
+	
+	for &character in &['=', ']', '"', ' ']
+	{
+		assert_eq!(StructuredDataParameterName::new(&format!("a{}b", character)), Err(StructuredDataNameError::InvalidCharacter { character: character, index: 1 }));
+	}
+}
@@ -0,0 +1,106 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+
+extern crate libc;
+use std::env::var_os;
+use std::path::Path;
+use rfc5424::StructuredDataElement;
+
+
+/// The parameters of a `timeQuality` element (RFC 5424 section 7.1)
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TimeQuality
+{
+	pub tzKnown: bool,
+	pub isSynced: bool,
+	
+	/// Microseconds; None if not synced or the kernel does not estimate an error
+	pub syncAccuracy: Option<u64>,
+}
+
+impl TimeQuality
+{
+	/// Queries the kernel clock state with `adjtimex()` (Linux) or `ntp_adjtime()` (BSDs and Mac OS X); on other platforms, the clock is reported as not synced
+	/// The timezone is known if `TZ` is set or `/etc/localtime` exists
+	pub fn current() -> TimeQuality
+	{
+		let (isSynced, syncAccuracy) = match kernelClockState()
+		{
+			None => (false, None),
+			Some(estimatedErrorInMicroseconds) => (true, estimatedErrorInMicroseconds),
+		};
+		
+		TimeQuality
+		{
+			tzKnown: isTimezoneKnown(),
+			isSynced: isSynced,
+			syncAccuracy: syncAccuracy,
+		}
+	}
+	
+	#[inline(always)]
+	pub fn element<'a>(&self) -> StructuredDataElement<'a>
+	{
+		StructuredDataElement::timeQuality(self.tzKnown, self.isSynced, self.syncAccuracy)
+	}
+}
+
+fn isTimezoneKnown() -> bool
+{
+	match var_os("TZ")
+	{
+		Some(ref timezone) if !timezone.is_empty() => true,
+		_ => Path::new("/etc/localtime").exists(),
+	}
+}
+
+/// None if not synced; otherwise the estimated error, if any, in microseconds
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd", target_os = "dragonfly", target_os = "netbsd", target_os = "openbsd"))]
+fn kernelClockState() -> Option<Option<u64>>
+{
+	use std::mem::zeroed;
+	use self::libc::timex;
+	use self::libc::STA_UNSYNC;
+	use self::libc::TIME_ERROR;
+	#[cfg(any(target_os = "linux", target_os = "android"))] use self::libc::adjtimex as ntp_adjtime;
+	#[cfg(not(any(target_os = "linux", target_os = "android")))] use self::libc::ntp_adjtime;
+	
+	// modes is 0, so this only reads the clock state
+	let mut state: timex = unsafe { zeroed() };
+	let result = unsafe { ntp_adjtime(&mut state) };
+	if result == -1 || result == TIME_ERROR || state.status & STA_UNSYNC != 0
+	{
+		return None;
+	}
+	
+	if state.esterror < 0
+	{
+		Some(None)
+	}
+	else
+	{
+		Some(Some(state.esterror as u64))
+	}
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd", target_os = "dragonfly", target_os = "netbsd", target_os = "openbsd")))]
+fn kernelClockState() -> Option<Option<u64>>
+{
+	None
+}
+
+#[test]
+fn syncAccuracyIsOnlyRenderedIfSynced()
+{
+	let render = |timeQuality: TimeQuality|
+	{
+		let mut rendered = Vec::new();
+		timeQuality.element().write(&mut rendered);
+		String::from_utf8(rendered).unwrap()
+	};
+	
+	assert_eq!(render(TimeQuality { tzKnown: false, isSynced: false, syncAccuracy: None }), "[timeQuality tzKnown=\"0\" isSynced=\"0\"]");
+	assert_eq!(render(TimeQuality { tzKnown: true, isSynced: true, syncAccuracy: None }), "[timeQuality tzKnown=\"1\" isSynced=\"1\"]");
+	assert_eq!(render(TimeQuality { tzKnown: true, isSynced: true, syncAccuracy: Some(1500) }), "[timeQuality tzKnown=\"1\" isSynced=\"1\" syncAccuracy=\"1500\"]");
+}
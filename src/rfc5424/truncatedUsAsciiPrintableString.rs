@@ -4,13 +4,15 @@
 
 extern crate string_utilities;
 use rfc5424::StructuredDataParameter;
+use rfc5424::structuredDataNameValidation::is_valid_sd_name_character;
 use self::string_utilities::DefaultUsAsciiReplacementCharacter;
 use self::string_utilities::to_8bit_encoding_replacement_function_us_ascii_printable;
 use self::string_utilities::to_8bit_encoding_string;
 use std::io::Write;
 use std::borrow::Cow;
+use std::str::from_utf8_unchecked;
 
-#[derive(Debug, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TruncatedUsAsciiPrintableString
 {
 	value: Vec<u8>
@@ -26,25 +28,60 @@ impl TruncatedUsAsciiPrintableString
 		}
 	}
 	
+	/// Panics if `sd_name` contains characters not permitted in a SDNAME; use `StructuredDataId::new()` or `StructuredDataParameterName::new()` to validate names from elsewhere
 	pub fn new_sd_name(sd_name: &str) -> TruncatedUsAsciiPrintableString
 	{
 		for character in sd_name.chars()
 		{
 			match character
 			{
-				'"' | '\\' | ']' | '\x00' ... '\x20' | '\x7F' => panic!("A SDNAME can not contain control codes, spaces, DEL, double quotes, slashes or closing square brackets"),
-				'\x21' ... '\x7E' => {},
+				'\x00' ... '\x7F' if !TruncatedUsAsciiPrintableString::is_valid_sd_name_character(character) => panic!("A SDNAME can not contain control codes, spaces, DEL, double quotes, equals signs, slashes or closing square brackets"),
+				'\x00' ... '\x7F' => {},
 				_ => panic!("A SDNAME can not contain Unicode characters that can not be converted to printable US-ASCII"),
 			}
 		}
 		TruncatedUsAsciiPrintableString::new(sd_name, 32)
 	}
+	
+	/// For names from elsewhere, such as field names; invalid characters are replaced with `_` rather than causing a panic, and an empty name becomes `_`
+	pub fn new_sd_name_replacing_invalid_characters(sd_name: &str) -> TruncatedUsAsciiPrintableString
+	{
+		let mut replaced: String = sd_name.chars().take(32).map(|character| if TruncatedUsAsciiPrintableString::is_valid_sd_name_character(character)
+		{
+			character
+		}
+		else
+		{
+			'_'
+		}).collect();
+		
+		if replaced.is_empty()
+		{
+			replaced.push('_');
+		}
+		
+		TruncatedUsAsciiPrintableString::new(&replaced, 32)
+	}
+	
+	/// Printable US-ASCII except space, double quote, equals, backslash and closing square bracket
+	#[inline(always)]
+	pub fn is_valid_sd_name_character(character: char) -> bool
+	{
+		is_valid_sd_name_character(character)
+	}
 
 	fn truncate_us_ascii_printable(string: &str, maximum_length: usize) -> String
 	{
 	    to_8bit_encoding_string(string, maximum_length, |character| to_8bit_encoding_replacement_function_us_ascii_printable(character, DefaultUsAsciiReplacementCharacter))
 	}
 	
+	/// Always succeeds, as only printable US-ASCII is ever stored
+	#[inline(always)]
+	pub fn as_str(&self) -> &str
+	{
+		unsafe { from_utf8_unchecked(&self.value) }
+	}
+	
 	pub fn parameter<'a>(&'a self, value: Cow<'a, str>) -> StructuredDataParameter<'a>
 	{
 		StructuredDataParameter::new(self, value)
@@ -0,0 +1,191 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::str::FromStr;
+use Facility;
+use LogMask;
+use Priority;
+use SelectorParseError;
+use Severity;
+use syslogSenders::Rfc3164Facility;
+
+
+const NumberOfRfc3164Facilities: usize = 24;
+
+/// A syslog.conf-style selector, eg `mail.warn;*.err;local0.!=debug;auth,authpriv.none`
+/// Semicolon-separated entries are applied left-to-right, so later entries override earlier ones, as in sysklogd and rsyslog:-
+/// - `facility.severity` adds `severity` and all those more severe
+/// - `facility.=severity` adds just `severity`
+/// - `facility.!severity` removes `severity` and all those more severe
+/// - `facility.!=severity` removes just `severity`
+/// - `facility.none` removes everything; `facility.*` adds everything
+/// Facilities may be comma-separated or `*`; `*` does not include the private use facility codes, which `Rfc3164Facility` can not represent
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Selector
+{
+	masks: [LogMask; NumberOfRfc3164Facilities],
+}
+
+impl Selector
+{
+	/// Matches nothing
+	pub fn none() -> Selector
+	{
+		Selector
+		{
+			masks: [LogMask::empty(); NumberOfRfc3164Facilities],
+		}
+	}
+	
+	/// Matches everything, ie `*.*`
+	pub fn all() -> Selector
+	{
+		Selector
+		{
+			masks: [LogMask::all(); NumberOfRfc3164Facilities],
+		}
+	}
+	
+	#[inline(always)]
+	pub fn matches(&self, rfc3164Facility: Rfc3164Facility, severity: Severity) -> bool
+	{
+		self.masks[rfc3164Facility as usize].contains(severity)
+	}
+	
+	/// Suitable for routing received messages once their PRI has been parsed
+	#[inline(always)]
+	pub fn matchesPriority(&self, priority: Priority) -> bool
+	{
+		self.matches(priority.rfc3164Facility(), priority.severity())
+	}
+	
+	#[inline(always)]
+	pub fn matchesFacility(&self, facility: Facility, severity: Severity) -> bool
+	{
+		self.matches(facility.toRfc3164Facility(), severity)
+	}
+	
+	/// The severities matched for `rfc3164Facility`
+	#[inline(always)]
+	pub fn mask(&self, rfc3164Facility: Rfc3164Facility) -> LogMask
+	{
+		self.masks[rfc3164Facility as usize]
+	}
+	
+	fn apply(&mut self, entry: &str) -> Result<(), SelectorParseError>
+	{
+		let separatorIndex = try!(entry.rfind('.').ok_or_else(|| SelectorParseError::MissingSeparator(entry.to_owned())));
+		let facilities = &entry[.. separatorIndex];
+		let severity = &entry[separatorIndex + 1 ..];
+		
+		let (negate, severity) = if severity.starts_with('!')
+		{
+			(true, &severity[1 ..])
+		}
+		else
+		{
+			(false, severity)
+		};
+		
+		let mask = match severity
+		{
+			"none" => LogMask::all(),
+			"*" => LogMask::all(),
+			_ if severity.starts_with('=') => LogMask::exactly(try!(Selector::parseSeverity(&severity[1 ..]))),
+			_ => LogMask::upto(try!(Selector::parseSeverity(severity))),
+		};
+		let remove = negate || severity == "none";
+		
+		for facility in facilities.split(',')
+		{
+			let facility = facility.trim();
+			if facility == "*"
+			{
+				for index in 0 .. NumberOfRfc3164Facilities
+				{
+					Selector::modify(&mut self.masks[index], mask, remove);
+				}
+			}
+			else
+			{
+				let rfc3164Facility = try!(Rfc3164Facility::fromName(facility).ok_or_else(|| SelectorParseError::UnknownFacility(facility.to_owned())));
+				Selector::modify(&mut self.masks[rfc3164Facility as usize], mask, remove);
+			}
+		}
+		
+		Ok(())
+	}
+	
+	#[inline(always)]
+	fn modify(existing: &mut LogMask, mask: LogMask, remove: bool)
+	{
+		if remove
+		{
+			*existing -= mask;
+		}
+		else
+		{
+			*existing |= mask;
+		}
+	}
+	
+	#[inline(always)]
+	fn parseSeverity(name: &str) -> Result<Severity, SelectorParseError>
+	{
+		Severity::fromName(name).ok_or_else(|| SelectorParseError::UnknownSeverity(name.to_owned()))
+	}
+}
+
+impl FromStr for Selector
+{
+	type Err = SelectorParseError;
+	
+	fn from_str(value: &str) -> Result<Self, Self::Err>
+	{
+		let mut selector = Selector::none();
+		let mut empty = true;
+		for entry in value.split(';').map(|entry| entry.trim()).filter(|entry| !entry.is_empty())
+		{
+			try!(selector.apply(entry));
+			empty = false;
+		}
+		
+		if empty
+		{
+			Err(SelectorParseError::Empty)
+		}
+		else
+		{
+			Ok(selector)
+		}
+	}
+}
+
+#[test]
+fn parsesAndMatchesSelectors()
+{
+	let selector = "mail.warn;*.err;local0.debug;local0.!=debug;auth,authpriv.none".parse::<Selector>().unwrap();
+	
+	assert!(selector.matches(Rfc3164Facility::mail, Severity::LOG_WARNING));
+	assert!(!selector.matches(Rfc3164Facility::mail, Severity::LOG_NOTICE));
+	assert!(selector.matches(Rfc3164Facility::user, Severity::LOG_ERR));
+	assert!(!selector.matches(Rfc3164Facility::user, Severity::LOG_WARNING));
+	assert!(selector.matches(Rfc3164Facility::local0, Severity::LOG_INFO));
+	assert!(!selector.matches(Rfc3164Facility::local0, Severity::LOG_DEBUG));
+	assert!(!selector.matches(Rfc3164Facility::auth, Severity::LOG_EMERG));
+	assert!(!selector.matches(Rfc3164Facility::authpriv, Severity::LOG_EMERG));
+	
+	let selector = "*.*;kern.!crit;user.=info".parse::<Selector>().unwrap();
+	assert!(!selector.matches(Rfc3164Facility::kern, Severity::LOG_ALERT));
+	assert!(selector.matches(Rfc3164Facility::kern, Severity::LOG_ERR));
+	assert!(selector.matchesPriority("<134>".parse().unwrap()));
+	
+	let selector = "uucp.*".parse::<Selector>().unwrap();
+	assert!(selector.matchesFacility(Facility::LOG_UUCP, Severity::LOG_DEBUG));
+	assert!(!selector.matchesFacility(Facility::LOG_NEWS, Severity::LOG_DEBUG));
+	
+	assert_eq!("".parse::<Selector>(), Err(SelectorParseError::Empty));
+	assert_eq!("mail".parse::<Selector>(), Err(SelectorParseError::MissingSeparator("mail".to_owned())));
+	assert_eq!("mail.loud".parse::<Selector>(), Err(SelectorParseError::UnknownSeverity("loud".to_owned())));
+	assert_eq!("post.info".parse::<Selector>(), Err(SelectorParseError::UnknownFacility("post".to_owned())));
+}
@@ -0,0 +1,38 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SelectorParseError
+{
+	Empty,
+	
+	/// A selector without a `.` separating facilities from severity
+	MissingSeparator(String),
+	
+	UnknownFacility(String),
+	UnknownSeverity(String),
+}
+
+impl Display for SelectorParseError
+{
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result
+	{
+		match *self
+		{
+			SelectorParseError::Empty => write!(formatter, "selector is empty"),
+			SelectorParseError::MissingSeparator(ref selector) => write!(formatter, "selector '{}' is not of the form 'facility.severity'", selector),
+			SelectorParseError::UnknownFacility(ref name) => write!(formatter, "facility '{}' is not known", name),
+			SelectorParseError::UnknownSeverity(ref name) => write!(formatter, "severity '{}' is not known", name),
+		}
+	}
+}
+
+impl Error for SelectorParseError
+{
+}
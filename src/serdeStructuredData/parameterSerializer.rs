@@ -0,0 +1,391 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate serde;
+use self::serde::Serialize;
+use self::serde::Serializer;
+use self::serde::ser::SerializeMap;
+use self::serde::ser::SerializeSeq;
+use self::serde::ser::SerializeStruct;
+use self::serde::ser::SerializeStructVariant;
+use self::serde::ser::SerializeTuple;
+use self::serde::ser::SerializeTupleStruct;
+use self::serde::ser::SerializeTupleVariant;
+use serdeStructuredData::SequenceRepresentation;
+use serdeStructuredData::SerializationError;
+use serdeStructuredData::SerializationOptions;
+
+
+/// Serializes a value into `(name, value)` parameter pairs; names are not validated
+/// A scalar with an empty `prefix` (ie at the top level) is an error, as it has no name
+pub struct ParameterSerializer<'a>
+{
+	parameters: &'a mut Vec<(String, String)>,
+	prefix: String,
+	options: &'a SerializationOptions,
+	
+	// The index of the next sequence element, or the key of the next map value
+	index: usize,
+	key: Option<String>,
+}
+
+impl <'a> ParameterSerializer<'a>
+{
+	pub fn new(parameters: &'a mut Vec<(String, String)>, prefix: String, options: &'a SerializationOptions) -> ParameterSerializer<'a>
+	{
+		ParameterSerializer
+		{
+			parameters: parameters,
+			prefix: prefix,
+			options: options,
+			index: 0,
+			key: None,
+		}
+	}
+	
+	fn scalar<V: ToString>(self, value: V) -> Result<(), SerializationError>
+	{
+		if self.prefix.is_empty()
+		{
+			return Err(SerializationError::NotAStructOrMap);
+		}
+		
+		self.parameters.push((self.prefix, value.to_string()));
+		Ok(())
+	}
+	
+	fn compound(self) -> Result<Self, SerializationError>
+	{
+		Ok(self)
+	}
+	
+	/// Sequences at the top level have no name for their elements
+	fn sequence(self) -> Result<Self, SerializationError>
+	{
+		if self.prefix.is_empty()
+		{
+			Err(SerializationError::NotAStructOrMap)
+		}
+		else
+		{
+			Ok(self)
+		}
+	}
+	
+	fn nestedName(&self, name: &str) -> String
+	{
+		if self.prefix.is_empty()
+		{
+			name.to_owned()
+		}
+		else
+		{
+			format!("{}{}{}", self.prefix, self.options.separator, name)
+		}
+	}
+	
+	fn serializeNested<T: ?Sized + Serialize>(&mut self, name: String, value: &T) -> Result<(), SerializationError>
+	{
+		value.serialize(ParameterSerializer::new(self.parameters, name, self.options))
+	}
+	
+	fn serializeElement<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerializationError>
+	{
+		let name = match self.options.sequenceRepresentation
+		{
+			SequenceRepresentation::Flatten => self.nestedName(&self.index.to_string()),
+			SequenceRepresentation::RepeatParameter => self.prefix.clone(),
+		};
+		self.index += 1;
+		self.serializeNested(name, value)
+	}
+	
+	fn serializeField<T: ?Sized + Serialize>(&mut self, name: &str, value: &T) -> Result<(), SerializationError>
+	{
+		let name = self.nestedName(name);
+		self.serializeNested(name, value)
+	}
+}
+
+impl <'a> Serializer for ParameterSerializer<'a>
+{
+	type Ok = ();
+	type Error = SerializationError;
+	type SerializeSeq = Self;
+	type SerializeTuple = Self;
+	type SerializeTupleStruct = Self;
+	type SerializeTupleVariant = Self;
+	type SerializeMap = Self;
+	type SerializeStruct = Self;
+	type SerializeStructVariant = Self;
+	
+	fn serialize_bool(self, value: bool) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_i8(self, value: i8) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_i16(self, value: i16) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_i32(self, value: i32) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_i64(self, value: i64) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_u8(self, value: u8) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_u16(self, value: u16) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_u32(self, value: u32) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_u64(self, value: u64) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_f32(self, value: f32) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_f64(self, value: f64) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_char(self, value: char) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	fn serialize_str(self, value: &str) -> Result<(), SerializationError>
+	{
+		self.scalar(value)
+	}
+	
+	/// Invalid UTF-8 is replaced
+	fn serialize_bytes(self, value: &[u8]) -> Result<(), SerializationError>
+	{
+		self.scalar(String::from_utf8_lossy(value))
+	}
+	
+	fn serialize_none(self) -> Result<(), SerializationError>
+	{
+		Ok(())
+	}
+	
+	fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<(), SerializationError>
+	{
+		value.serialize(self)
+	}
+	
+	fn serialize_unit(self) -> Result<(), SerializationError>
+	{
+		Ok(())
+	}
+	
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerializationError>
+	{
+		Ok(())
+	}
+	
+	fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<(), SerializationError>
+	{
+		self.scalar(variant)
+	}
+	
+	fn serialize_newtype_struct<T: ?Sized + Serialize>(self, _name: &'static str, value: &T) -> Result<(), SerializationError>
+	{
+		value.serialize(self)
+	}
+	
+	fn serialize_newtype_variant<T: ?Sized + Serialize>(self, _name: &'static str, _variant_index: u32, _variant: &'static str, value: &T) -> Result<(), SerializationError>
+	{
+		value.serialize(self)
+	}
+	
+	fn serialize_seq(self, _len: Option<usize>) -> Result<Self, SerializationError>
+	{
+		self.sequence()
+	}
+	
+	fn serialize_tuple(self, _len: usize) -> Result<Self, SerializationError>
+	{
+		self.sequence()
+	}
+	
+	fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, SerializationError>
+	{
+		self.sequence()
+	}
+	
+	fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self, SerializationError>
+	{
+		self.sequence()
+	}
+	
+	fn serialize_map(self, _len: Option<usize>) -> Result<Self, SerializationError>
+	{
+		self.compound()
+	}
+	
+	fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, SerializationError>
+	{
+		self.compound()
+	}
+	
+	fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize) -> Result<Self, SerializationError>
+	{
+		self.compound()
+	}
+}
+
+impl <'a> SerializeSeq for ParameterSerializer<'a>
+{
+	type Ok = ();
+	type Error = SerializationError;
+	
+	fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerializationError>
+	{
+		self.serializeElement(value)
+	}
+	
+	fn end(self) -> Result<(), SerializationError>
+	{
+		Ok(())
+	}
+}
+
+impl <'a> SerializeTuple for ParameterSerializer<'a>
+{
+	type Ok = ();
+	type Error = SerializationError;
+	
+	fn serialize_element<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerializationError>
+	{
+		self.serializeElement(value)
+	}
+	
+	fn end(self) -> Result<(), SerializationError>
+	{
+		Ok(())
+	}
+}
+
+impl <'a> SerializeTupleStruct for ParameterSerializer<'a>
+{
+	type Ok = ();
+	type Error = SerializationError;
+	
+	fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerializationError>
+	{
+		self.serializeElement(value)
+	}
+	
+	fn end(self) -> Result<(), SerializationError>
+	{
+		Ok(())
+	}
+}
+
+impl <'a> SerializeTupleVariant for ParameterSerializer<'a>
+{
+	type Ok = ();
+	type Error = SerializationError;
+	
+	fn serialize_field<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerializationError>
+	{
+		self.serializeElement(value)
+	}
+	
+	fn end(self) -> Result<(), SerializationError>
+	{
+		Ok(())
+	}
+}
+
+impl <'a> SerializeMap for ParameterSerializer<'a>
+{
+	type Ok = ();
+	type Error = SerializationError;
+	
+	/// Keys are serialized using a `ParameterSerializer` too, and must produce exactly one parameter
+	fn serialize_key<T: ?Sized + Serialize>(&mut self, key: &T) -> Result<(), SerializationError>
+	{
+		const KeyPrefix: &'static str = "key";
+		
+		let mut parameters = Vec::with_capacity(1);
+		try!(key.serialize(ParameterSerializer::new(&mut parameters, KeyPrefix.to_owned(), self.options)));
+		if parameters.len() != 1 || parameters[0].0 != KeyPrefix
+		{
+			return Err(SerializationError::KeyIsNotAScalar);
+		}
+		
+		self.key = parameters.pop().map(|(_, key)| key);
+		Ok(())
+	}
+	
+	fn serialize_value<T: ?Sized + Serialize>(&mut self, value: &T) -> Result<(), SerializationError>
+	{
+		let key = self.key.take().expect("serialize_key() must be called before serialize_value()");
+		self.serializeField(&key, value)
+	}
+	
+	fn end(self) -> Result<(), SerializationError>
+	{
+		Ok(())
+	}
+}
+
+impl <'a> SerializeStruct for ParameterSerializer<'a>
+{
+	type Ok = ();
+	type Error = SerializationError;
+	
+	fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), SerializationError>
+	{
+		self.serializeField(key, value)
+	}
+	
+	fn end(self) -> Result<(), SerializationError>
+	{
+		Ok(())
+	}
+}
+
+impl <'a> SerializeStructVariant for ParameterSerializer<'a>
+{
+	type Ok = ();
+	type Error = SerializationError;
+	
+	fn serialize_field<T: ?Sized + Serialize>(&mut self, key: &'static str, value: &T) -> Result<(), SerializationError>
+	{
+		self.serializeField(key, value)
+	}
+	
+	fn end(self) -> Result<(), SerializationError>
+	{
+		Ok(())
+	}
+}
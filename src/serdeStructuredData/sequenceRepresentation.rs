@@ -0,0 +1,23 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+
+/// How sequence (and tuple) fields are turned into parameters
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SequenceRepresentation
+{
+	/// Each element becomes a parameter named with its index, eg `tags.0="a" tags.1="b"`
+	Flatten,
+	
+	/// Each element becomes a parameter with the same name, eg `tags="a" tags="b"`, which RFC 5424 permits
+	RepeatParameter,
+}
+
+impl Default for SequenceRepresentation
+{
+	#[inline(always)]
+	fn default() -> SequenceRepresentation
+	{
+		SequenceRepresentation::Flatten
+	}
+}
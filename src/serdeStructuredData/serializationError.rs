@@ -0,0 +1,54 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate serde;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use rfc5424::StructuredDataNameError;
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SerializationError
+{
+	/// Only structs and maps (and options, newtypes or enum variants wrapping them) can become elements
+	NotAStructOrMap,
+	
+	/// Map keys must serialize as a scalar, such as a string or integer
+	KeyIsNotAScalar,
+	
+	InvalidId(StructuredDataNameError),
+	
+	/// Names of nested fields may become longer than 32 characters, or contain characters permitted in Rust but not in PARAM-NAMEs
+	InvalidParameterName(String, StructuredDataNameError),
+	
+	Custom(String),
+}
+
+impl Display for SerializationError
+{
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result
+	{
+		match *self
+		{
+			SerializationError::NotAStructOrMap => write!(formatter, "only structs and maps can be serialized as a structured data element"),
+			SerializationError::KeyIsNotAScalar => write!(formatter, "map keys must be scalars"),
+			SerializationError::InvalidId(ref error) => write!(formatter, "invalid SD-ID: {}", error),
+			SerializationError::InvalidParameterName(ref name, ref error) => write!(formatter, "invalid PARAM-NAME '{}': {}", name, error),
+			SerializationError::Custom(ref message) => write!(formatter, "{}", message),
+		}
+	}
+}
+
+impl Error for SerializationError
+{
+}
+
+impl self::serde::ser::Error for SerializationError
+{
+	fn custom<T: Display>(message: T) -> Self
+	{
+		SerializationError::Custom(message.to_string())
+	}
+}
@@ -0,0 +1,87 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate serde;
+use self::serde::Serialize;
+use serdeStructuredData::ParameterSerializer;
+use serdeStructuredData::SerializationError;
+use serdeStructuredData::SerializationOptions;
+use rfc5424::OwnedStructuredDataElement;
+
+
+/// Fields become PARAM-NAMEs and scalars are stringified; `None` and unit fields are omitted
+/// Nested structs and maps are flattened, joining names with `options.separator`; sequences are represented according to `options.sequenceRepresentation`
+/// eg `to_structured_data_element("request@38188", &request, &SerializationOptions::default())`
+pub fn to_structured_data_element<T: ?Sized + Serialize>(id: &str, value: &T, options: &SerializationOptions) -> Result<OwnedStructuredDataElement, SerializationError>
+{
+	let mut element = try!(OwnedStructuredDataElement::try_new(id).map_err(SerializationError::InvalidId));
+	
+	let mut parameters = Vec::new();
+	try!(value.serialize(ParameterSerializer::new(&mut parameters, String::new(), options)));
+	
+	for (name, value) in parameters
+	{
+		element = match element.try_param(&name, value)
+		{
+			Ok(element) => element,
+			Err(error) => return Err(SerializationError::InvalidParameterName(name, error)),
+		};
+	}
+	
+	Ok(element)
+}
+
+#[test]
+fn flattensNestedStructsAndSequences()
+{
+	use serdeStructuredData::SequenceRepresentation;
+	use self::serde::ser::SerializeStruct;
+	use self::serde::Serializer;
+	
+	struct Path;
+	
+	impl Serialize for Path
+	{
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		{
+			let mut state = try!(serializer.serialize_struct("Path", 2));
+			try!(state.serialize_field("route", "/users"));
+			try!(state.serialize_field("query", &None::<String>));
+			state.end()
+		}
+	}
+	
+	struct Request;
+	
+	impl Serialize for Request
+	{
+		fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		{
+			let mut state = try!(serializer.serialize_struct("Request", 3));
+			try!(state.serialize_field("id", &42u64));
+			try!(state.serialize_field("path", &Path));
+			try!(state.serialize_field("tags", &["a", "b"]));
+			state.end()
+		}
+	}
+	
+	let render = |element: OwnedStructuredDataElement|
+	{
+		let mut rendered = Vec::new();
+		element.write(&mut rendered);
+		String::from_utf8(rendered).unwrap()
+	};
+	
+	let flattened = to_structured_data_element("request@38188", &Request, &SerializationOptions::default()).unwrap();
+	assert_eq!(render(flattened), "[request@38188 id=\"42\" path.route=\"/users\" tags.0=\"a\" tags.1=\"b\"]");
+	
+	let options = SerializationOptions
+	{
+		separator: "_".to_owned(),
+		sequenceRepresentation: SequenceRepresentation::RepeatParameter,
+	};
+	let repeated = to_structured_data_element("request@38188", &Request, &options).unwrap();
+	assert_eq!(render(repeated), "[request@38188 id=\"42\" path_route=\"/users\" tags=\"a\" tags=\"b\"]");
+	
+	assert_eq!(to_structured_data_element("request@38188", &42, &SerializationOptions::default()), Err(SerializationError::NotAStructOrMap));
+}
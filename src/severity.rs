@@ -5,12 +5,22 @@ extern crate libc;
 
 use Facility;
 use Priority;
+use LocalPriority;
 use LogMask;
-use super::syslog2Senders::Rfc3164Facility;
+use syslogSenders::Rfc3164Facility;
 use self::libc::c_int;
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::iter::Cloned;
+use std::slice::Iter;
+use std::str::FromStr;
+use SeverityParseError;
 
 #[allow(non_camel_case_types)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(i32)] // We'd like to use c_int here, but the compiler won't let us
 #[cfg(not(target_os = "windows"))]
 pub enum Severity
@@ -27,7 +37,7 @@ pub enum Severity
 
 /// Windows values are 'fakes' to allow some compatibility
 #[allow(non_camel_case_types)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(i32)] // We'd like to use c_int here, but the compiler won't let us
 #[cfg(target_os = "windows")]
 pub enum Severity
@@ -46,22 +56,195 @@ pub enum Severity
 #[inline(always)]
 const fn LOG_MASK(pri: Severity) -> LogMask
 {
-	1 << (pri as c_int)
+	LogMask::from_bits_truncate(1 << (pri as c_int))
 }
 
 /// Always inlined to replicate the behaviour of a C 'function' macro
 #[inline(always)]
 const fn LOG_UPTO(toppri: Severity) -> LogMask
 {
-	(1 << ((toppri as c_int) + 1)) - 1
+	LogMask::from_bits_truncate((1 << ((toppri as c_int) + 1)) - 1)
 }
 
 impl Severity
 {
+	/// All severities, from most severe (LOG_EMERG) to least severe (LOG_DEBUG)
+	pub const All: [Severity; 8] = [Severity::LOG_EMERG, Severity::LOG_ALERT, Severity::LOG_CRIT, Severity::LOG_ERR, Severity::LOG_WARNING, Severity::LOG_NOTICE, Severity::LOG_INFO, Severity::LOG_DEBUG];
+	
+	/// Iterates from most severe (LOG_EMERG) to least severe (LOG_DEBUG)
+	#[inline(always)]
+	pub fn iter() -> Cloned<Iter<'static, Severity>>
+	{
+		Severity::All.iter().cloned()
+	}
+	
+	/// Returns None if `wire` is not in the range 0 to 7 inclusive
+	pub fn fromWire(wire: i32) -> Option<Severity>
+	{
+		match wire
+		{
+			0 => Some(Severity::LOG_EMERG),
+			1 => Some(Severity::LOG_ALERT),
+			2 => Some(Severity::LOG_CRIT),
+			3 => Some(Severity::LOG_ERR),
+			4 => Some(Severity::LOG_WARNING),
+			5 => Some(Severity::LOG_NOTICE),
+			6 => Some(Severity::LOG_INFO),
+			7 => Some(Severity::LOG_DEBUG),
+			_ => None,
+		}
+	}
+	
+	/// The RFC 3164 / RFC 5424 severity code; this is independent of the platform's libc values
+	pub fn toWire(self) -> i32
+	{
+		match self
+		{
+			Severity::LOG_EMERG => 0,
+			Severity::LOG_ALERT => 1,
+			Severity::LOG_CRIT => 2,
+			Severity::LOG_ERR => 3,
+			Severity::LOG_WARNING => 4,
+			Severity::LOG_NOTICE => 5,
+			Severity::LOG_INFO => 6,
+			Severity::LOG_DEBUG => 7,
+		}
+	}
+	
+	/// Returns None if `local` is not one of this platform's libc severity values
+	pub fn fromLocal(local: c_int) -> Option<Severity>
+	{
+		Severity::iter().find(|severity| (*severity as c_int) == local)
+	}
+	
+	/// The name used in selectors, eg `warning`
+	pub fn name(self) -> &'static str
+	{
+		match self
+		{
+			Severity::LOG_EMERG => "emerg",
+			Severity::LOG_ALERT => "alert",
+			Severity::LOG_CRIT => "crit",
+			Severity::LOG_ERR => "err",
+			Severity::LOG_WARNING => "warning",
+			Severity::LOG_NOTICE => "notice",
+			Severity::LOG_INFO => "info",
+			Severity::LOG_DEBUG => "debug",
+		}
+	}
+	
+	/// Also accepts the common aliases `panic` (LOG_EMERG), `error` (LOG_ERR) and `warn` (LOG_WARNING)
+	pub fn fromName(name: &str) -> Option<Severity>
+	{
+		match name
+		{
+			"emerg" | "panic" => Some(Severity::LOG_EMERG),
+			"alert" => Some(Severity::LOG_ALERT),
+			"crit" => Some(Severity::LOG_CRIT),
+			"err" | "error" => Some(Severity::LOG_ERR),
+			"warning" | "warn" => Some(Severity::LOG_WARNING),
+			"notice" => Some(Severity::LOG_NOTICE),
+			"info" => Some(Severity::LOG_INFO),
+			"debug" => Some(Severity::LOG_DEBUG),
+			_ => None,
+		}
+	}
+	
+	/// ie `self >= other`; LOG_EMERG is the most severe
 	#[inline(always)]
-	pub const fn toPriorityForCurrentLoggingFacility(self) -> Priority
+	pub fn is_at_least_as_severe_as(self, other: Severity) -> bool
 	{
-		self as c_int
+		self >= other
+	}
+	
+	/// Maps java.util.logging.Level integer values; SEVERE (1000) and above is LOG_ERR, CONFIG (700) and below is LOG_DEBUG
+	pub fn fromJavaLevel(level: i32) -> Severity
+	{
+		match level
+		{
+			_ if level >= 1000 => Severity::LOG_ERR,
+			_ if level >= 900 => Severity::LOG_WARNING,
+			_ if level >= 800 => Severity::LOG_INFO,
+			_ => Severity::LOG_DEBUG,
+		}
+	}
+	
+	/// Maps to java.util.logging.Level integer values SEVERE (1000), WARNING (900), INFO (800) and FINE (500)
+	pub fn toJavaLevel(self) -> i32
+	{
+		match self
+		{
+			Severity::LOG_EMERG | Severity::LOG_ALERT | Severity::LOG_CRIT | Severity::LOG_ERR => 1000,
+			Severity::LOG_WARNING => 900,
+			Severity::LOG_NOTICE | Severity::LOG_INFO => 800,
+			Severity::LOG_DEBUG => 500,
+		}
+	}
+	
+	/// Maps Python logging levels; CRITICAL (50) and above is LOG_CRIT, below INFO (20) is LOG_DEBUG
+	pub fn fromPythonLevel(level: i32) -> Severity
+	{
+		match level
+		{
+			_ if level >= 50 => Severity::LOG_CRIT,
+			_ if level >= 40 => Severity::LOG_ERR,
+			_ if level >= 30 => Severity::LOG_WARNING,
+			_ if level >= 20 => Severity::LOG_INFO,
+			_ => Severity::LOG_DEBUG,
+		}
+	}
+	
+	/// Maps to Python logging levels CRITICAL (50), ERROR (40), WARNING (30), INFO (20) and DEBUG (10)
+	pub fn toPythonLevel(self) -> i32
+	{
+		match self
+		{
+			Severity::LOG_EMERG | Severity::LOG_ALERT | Severity::LOG_CRIT => 50,
+			Severity::LOG_ERR => 40,
+			Severity::LOG_WARNING => 30,
+			Severity::LOG_NOTICE | Severity::LOG_INFO => 20,
+			Severity::LOG_DEBUG => 10,
+		}
+	}
+	
+	/// Maps an OpenTelemetry SeverityNumber (1 - 24 inclusive); returns None for SEVERITY_NUMBER_UNSPECIFIED (0) and out-of-range values
+	/// TRACE and DEBUG (1 - 8) are LOG_DEBUG, INFO (9) is LOG_INFO, INFO2 - INFO4 (10 - 12) are LOG_NOTICE, WARN (13 - 16) is LOG_WARNING, ERROR is LOG_ERR (17), LOG_CRIT (18) or LOG_ALERT (19 - 20) and FATAL (21 - 24) is LOG_EMERG
+	pub fn fromOpenTelemetrySeverityNumber(severityNumber: i32) -> Option<Severity>
+	{
+		match severityNumber
+		{
+			1 ... 8 => Some(Severity::LOG_DEBUG),
+			9 => Some(Severity::LOG_INFO),
+			10 ... 12 => Some(Severity::LOG_NOTICE),
+			13 ... 16 => Some(Severity::LOG_WARNING),
+			17 => Some(Severity::LOG_ERR),
+			18 => Some(Severity::LOG_CRIT),
+			19 ... 20 => Some(Severity::LOG_ALERT),
+			21 ... 24 => Some(Severity::LOG_EMERG),
+			_ => None,
+		}
+	}
+	
+	/// The inverse of `fromOpenTelemetrySeverityNumber()`
+	pub fn toOpenTelemetrySeverityNumber(self) -> i32
+	{
+		match self
+		{
+			Severity::LOG_EMERG => 21,
+			Severity::LOG_ALERT => 19,
+			Severity::LOG_CRIT => 18,
+			Severity::LOG_ERR => 17,
+			Severity::LOG_WARNING => 13,
+			Severity::LOG_NOTICE => 10,
+			Severity::LOG_INFO => 9,
+			Severity::LOG_DEBUG => 5,
+		}
+	}
+	
+	#[inline(always)]
+	pub const fn toPriorityForCurrentLoggingFacility(self) -> LocalPriority
+	{
+		LocalPriority::forCurrentLoggingFacility(self)
 	}
 	
 	/// Similar to LOG_MAKEPRI on all systems except:-
@@ -70,15 +253,16 @@ impl Severity
 	/// - incorrectly defined in musl to ((f as c_int) << 3) | (p as c_int); see the mailing list posts as to why: http://www.openwall.com/lists/musl/2015/10/12/2
 	/// - historically differed in glibc
 	#[inline(always)]
-	pub const fn toPriority(self, facility: Facility) -> Priority
+	pub const fn toPriority(self, facility: Facility) -> LocalPriority
 	{
-		(facility as c_int) | (self as c_int)
+		LocalPriority::new(facility, self)
 	}
 
+	/// Unlike `Facility`, `Rfc3164Facility` values are not pre-shifted
 	#[inline(always)]
 	pub const fn toPriorityRfc3164(self, rfc3164Facility: Rfc3164Facility) -> Priority
 	{
-		(rfc3164Facility as c_int) | (self as c_int)
+		Priority::new(rfc3164Facility, self)
 	}
 
 	/// If a message is masked then it is recorded, otherwise it is dropped
@@ -94,19 +278,101 @@ impl Severity
 	/// This is a little counter-intuitive - it's the reverse of what many people think of
 	/// eg specifying LOG_ERR in the priority logs LOG_EMERG, LOG_CRIT and LOG_ERR
 	/// Always inlined to replicate the behaviour of a C 'function' macro
+	#[deprecated(note = "use mask_at_least_as_severe_as(), which has the same behaviour")]
 	#[inline(always)]
 	pub const fn mask_upto(self) -> LogMask
 	{
 		LOG_UPTO(self)
 	}
 	
+	/// Creates a log mask that includes this severity and all those more severe than it
+	/// eg LOG_ERR masks LOG_EMERG, LOG_ALERT, LOG_CRIT and LOG_ERR
+	/// Always inlined to replicate the behaviour of the C 'function' macro LOG_UPTO
+	#[inline(always)]
+	pub const fn mask_at_least_as_severe_as(self) -> LogMask
+	{
+		LOG_UPTO(self)
+	}
+	
 	pub const fn mask_all_but_self(self) -> LogMask
 	{
-		!self.mask()
+		LogMask::from_bits_truncate(!(1 << (self as c_int)))
 	}
 	
 	pub const fn mask_and(self, other: Severity) -> LogMask
 	{
-		self.mask() | other.mask()
+		LogMask::from_bits_truncate((1 << (self as c_int)) | (1 << (other as c_int)))
+	}
+}
+
+impl PartialOrd for Severity
+{
+	#[inline(always)]
+	fn partial_cmp(&self, other: &Severity) -> Option<Ordering>
+	{
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Severity
+{
+	/// More severe compares greater, ie LOG_EMERG > LOG_DEBUG (the reverse of the numeric values)
+	#[inline(always)]
+	fn cmp(&self, other: &Severity) -> Ordering
+	{
+		other.toWire().cmp(&self.toWire())
+	}
+}
+
+impl Display for Severity
+{
+	/// Formats using the canonical name, eg `warning`
+	#[inline(always)]
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result
+	{
+		formatter.write_str(self.name())
+	}
+}
+
+impl FromStr for Severity
+{
+	type Err = SeverityParseError;
+	
+	/// Parses canonical names and aliases (see `fromName()`); case-sensitive, as are syslog.conf selectors
+	fn from_str(value: &str) -> Result<Self, Self::Err>
+	{
+		Severity::fromName(value).ok_or_else(|| SeverityParseError::UnknownName(value.to_owned()))
+	}
+}
+
+impl TryFrom<i32> for Severity
+{
+	type Error = SeverityParseError;
+	
+	/// From the RFC 3164 / RFC 5424 numeric severity code
+	#[inline(always)]
+	fn try_from(value: i32) -> Result<Self, Self::Error>
+	{
+		Severity::fromWire(value).ok_or(SeverityParseError::OutOfRange(value))
+	}
+}
+
+#[test]
+fn ordersMoreSevereAsGreaterAndParsesAliases()
+{
+	assert!(Severity::LOG_EMERG > Severity::LOG_DEBUG);
+	assert!(Severity::LOG_ERR.is_at_least_as_severe_as(Severity::LOG_WARNING));
+	assert!(!Severity::LOG_INFO.is_at_least_as_severe_as(Severity::LOG_NOTICE));
+	assert_eq!(Severity::iter().max(), Some(Severity::LOG_EMERG));
+	
+	assert_eq!("error".parse::<Severity>(), Ok(Severity::LOG_ERR));
+	assert_eq!("warn".parse::<Severity>(), Ok(Severity::LOG_WARNING));
+	assert_eq!(Severity::LOG_WARNING.to_string(), "warning");
+	assert_eq!(Severity::try_from(8), Err(SeverityParseError::OutOfRange(8)));
+	
+	for severity in Severity::iter()
+	{
+		assert_eq!(Severity::fromOpenTelemetrySeverityNumber(severity.toOpenTelemetrySeverityNumber()), Some(severity));
+		assert_eq!(Severity::try_from(severity.toWire()), Ok(severity));
 	}
 }
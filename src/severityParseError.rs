@@ -0,0 +1,31 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeverityParseError
+{
+	UnknownName(String),
+	OutOfRange(i32),
+}
+
+impl Display for SeverityParseError
+{
+	fn fmt(&self, formatter: &mut Formatter) -> fmt::Result
+	{
+		match *self
+		{
+			SeverityParseError::UnknownName(ref name) => write!(formatter, "severity '{}' is not known", name),
+			SeverityParseError::OutOfRange(value) => write!(formatter, "severity {} is not in the range 0 to 7 inclusive", value),
+		}
+	}
+}
+
+impl Error for SeverityParseError
+{
+}
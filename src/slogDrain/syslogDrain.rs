@@ -0,0 +1,124 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate slog;
+use std::borrow::Cow;
+use std::io::Error;
+use self::slog::Drain;
+use self::slog::KV;
+use self::slog::Level;
+use self::slog::OwnedKVList;
+use self::slog::Record;
+use slogDrain::KeyValueSerializer;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use rfc5424::StructuredData;
+use rfc5424::StructuredDataElement;
+use rfc5424::StructuredDataParameter;
+use rfc5424::TruncatedUsAsciiPrintableString;
+use Severity;
+use MessageHeaderOverrides;
+
+
+/// A `slog::Drain` that sends records using any `SyslogSender`
+/// The key-value pairs of the record and of its logger become the parameters of a `fields@<PEN>` element, and its tag, if any, the MSGID
+/// The module, file and line of each record are attached as a `source@38188` element
+/// Keys are made into valid SD-NAMEs by replacing invalid characters with `_`
+#[derive(Debug)]
+pub struct SyslogDrain<S: SyslogSender>
+{
+	sender: S,
+	rfc3164Facility: Rfc3164Facility,
+	fieldsSdId: TruncatedUsAsciiPrintableString,
+}
+
+impl <S: SyslogSender> SyslogDrain<S>
+{
+	/// `privateEnterpriseNumber` is used to form the private SD-ID of key-value pairs, eg StormMQ's is 38188
+	pub fn new(sender: S, rfc3164Facility: Rfc3164Facility, privateEnterpriseNumber: u32) -> SyslogDrain<S>
+	{
+		SyslogDrain
+		{
+			sender: sender,
+			rfc3164Facility: rfc3164Facility,
+			fieldsSdId: TruncatedUsAsciiPrintableString::new_sd_name(&format!("fields@{}", privateEnterpriseNumber)),
+		}
+	}
+	
+	#[inline(always)]
+	fn severity(level: Level) -> Severity
+	{
+		match level
+		{
+			Level::Critical => Severity::LOG_CRIT,
+			Level::Error => Severity::LOG_ERR,
+			Level::Warning => Severity::LOG_WARNING,
+			Level::Info => Severity::LOG_INFO,
+			Level::Debug => Severity::LOG_DEBUG,
+			Level::Trace => Severity::LOG_DEBUG,
+		}
+	}
+}
+
+impl <S: SyslogSender> Drain for SyslogDrain<S>
+{
+	type Ok = ();
+	type Err = Error;
+	
+	fn log(&self, record: &Record, values: &OwnedKVList) -> Result<(), Error>
+	{
+		let mut keyValueSerializer = KeyValueSerializer::new();
+		try!(record.kv().serialize(record, &mut keyValueSerializer));
+		try!(values.serialize(record, &mut keyValueSerializer));
+		
+		let parameterNames: Vec<TruncatedUsAsciiPrintableString> = keyValueSerializer.fields.iter().map(|&(ref name, _)| TruncatedUsAsciiPrintableString::new_sd_name_replacing_invalid_characters(name)).collect();
+		
+		let mut structuredData = StructuredData::new();
+		
+		if !parameterNames.is_empty()
+		{
+			let parameters: Vec<StructuredDataParameter> = parameterNames.iter().zip(keyValueSerializer.fields.iter()).map(|(name, &(_, ref value))| name.parameter(Cow::Borrowed(&value[..]))).collect();
+			try!(structuredData.insert(StructuredDataElement::new(&self.fieldsSdId, parameters)));
+		}
+		
+		try!(structuredData.insert(StructuredDataElement::source(Some(record.module()), Some(record.file()), Some(record.line()))));
+		
+		let messageHeaderOverrides = match record.tag()
+		{
+			"" => MessageHeaderOverrides::default(),
+			tag => MessageHeaderOverrides::messageId(tag),
+		};
+		
+		let message = record.msg().to_string();
+		
+		self.sender.sendWithOverrides(self.rfc3164Facility, SyslogDrain::<S>::severity(record.level()), &messageHeaderOverrides, &structuredData, &message)
+	}
+}
+
+#[test]
+fn sendsRecordsWithTheirKeyValuePairs()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let syslogDrain = SyslogDrain::new(CapturingSyslogSender::forTesting(), Rfc3164Facility::local0, 32473);
+	let values = OwnedKVList::from(self::slog::o!("service" => "api"));
+	
+	syslogDrain.log(&self::slog::record!(Level::Warning, "audit", &format_args!("hello {}", "world"), self::slog::b!("user name" => "bob")), &values).unwrap();
+	syslogDrain.log(&self::slog::record!(Level::Trace, "", &format_args!("traced"), self::slog::b!()), &values).unwrap();
+	
+	let capturedMessages = syslogDrain.sender.messages();
+	assert_eq!(capturedMessages.len(), 2);
+	
+	let capturedMessage = &capturedMessages[0];
+	assert_eq!(capturedMessage.message, "hello world");
+	assert_eq!(capturedMessage.severity, Severity::LOG_WARNING);
+	assert_eq!(capturedMessage.rfc3164Facility, Rfc3164Facility::local0);
+	assert_eq!(capturedMessage.messageId, Some("audit".to_owned()));
+	assert_eq!(capturedMessage.structuredDataParameter("fields@32473", "user_name"), Some("bob"));
+	assert_eq!(capturedMessage.structuredDataParameter("fields@32473", "service"), Some("api"));
+	assert_eq!(capturedMessage.structuredDataParameter("source@38188", "modulePath"), Some(module_path!()));
+	
+	let capturedMessage = &capturedMessages[1];
+	assert_eq!(capturedMessage.severity, Severity::LOG_DEBUG);
+	assert_eq!(capturedMessage.messageId, None);
+}
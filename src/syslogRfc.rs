@@ -6,15 +6,26 @@ use std::io::Write;
 use self::time::Tm;
 use rfc5424::TruncatedUsAsciiPrintableString;
 use rfc5424::StructuredData;
-use syslog2Senders::Rfc3164Facility;
+use syslogSenders::Rfc3164Facility;
 use rfc5424::write_structured_data_elements;
 use Severity;
 use rfc5424::truncatedUsAsciiPrintableString::WriteTruncatedUsAsciiPrintableString;
 use VecU8PushStr;
+use MessageHeaderOverrides;
+use ForkAwareProcessId;
+use HostName;
 
 
+const NILVALUE: &'static str = "-";
+
 const SyslogProtocolVersion: &'static [u8] = b"1";
 
+const Rfc3164MaximumAppNameLength: usize = 32;
+
+const Rfc5424MaximumAppNameLength: usize = 48;
+
+const Rfc5424MaximumMessageIdLength: usize = 32;
+
 lazy_static!
 {
 	static ref SyslogMonths: Vec<&'static str> = new();
@@ -27,36 +38,68 @@ fn new() -> Vec<&'static str>
 	vec!["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"]
 }
 
+fn is_longer_than(value: Option<&str>, maximum_length: usize) -> bool
+{
+	match value
+	{
+		None => false,
+		Some(value) => value.chars().count() > maximum_length,
+	}
+}
+
+fn write_overridable(writer: &mut Vec<u8>, value: &TruncatedUsAsciiPrintableString, overriddenBy: Option<&str>, maximum_length: usize)
+{
+	match overriddenBy
+	{
+		None => writer.write_truncated(value),
+		Some(overriddenBy) => writer.write_truncated(&TruncatedUsAsciiPrintableString::new(if overriddenBy.is_empty()
+		{
+			NILVALUE
+		}
+		else
+		{
+			overriddenBy
+		}, maximum_length)),
+	}
+}
+
 // Duplication of fields seems unpleasant - doubles the enum width
 #[derive(Debug)]
 pub enum SyslogRfc
 {
 	Rfc3164
 	{
-		hostNameWithoutDomain: TruncatedUsAsciiPrintableString,
+		hostNameWithoutDomain: HostName,
 		appName: TruncatedUsAsciiPrintableString,
-		processId: TruncatedUsAsciiPrintableString,
+		processId: ForkAwareProcessId,
 	},
 	Rfc5424
 	{
-		hostName: TruncatedUsAsciiPrintableString,
+		hostName: HostName,
 		appName: TruncatedUsAsciiPrintableString,
-		processId: TruncatedUsAsciiPrintableString,
+		processId: ForkAwareProcessId,
 		messageId: TruncatedUsAsciiPrintableString,
 	},
 }
 
 impl SyslogRfc
 {
+	/// True if `write()` would truncate a header field in `messageHeaderOverrides`; header fields given at construction are truncated then, once
+	pub fn truncates(&self, messageHeaderOverrides: &MessageHeaderOverrides) -> bool
+	{
+		match *self
+		{
+			SyslogRfc::Rfc3164 { .. } => is_longer_than(messageHeaderOverrides.appName, Rfc3164MaximumAppNameLength),
+			SyslogRfc::Rfc5424 { .. } => is_longer_than(messageHeaderOverrides.appName, Rfc5424MaximumAppNameLength) || is_longer_than(messageHeaderOverrides.messageId, Rfc5424MaximumMessageIdLength),
+		}
+	}
+	
 	// The match in here seems wrong - surely we should prefer dispatch?
-	pub fn write(&self, time: Tm, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Vec<u8>
+	pub fn write(&self, time: Tm, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Vec<u8>
 	{
 		let mut writer: Vec<u8> = Vec::with_capacity(4096);
 
-		writer.push(b'<');
-		writer.push_str(&severity.toPriorityRfc3164(rfc3164Facility).to_string());
-		writer.push(b'>');
-		writer.push(b' ');
+		write!(&mut writer, "{}", severity.toPriorityRfc3164(rfc3164Facility));
 		
 		match *self
 		{
@@ -64,12 +107,12 @@ impl SyslogRfc
 			{
 				write!(&mut writer, "{} {:02} {:04}:{:02}:{:02} ", SyslogMonths[time.tm_mon as usize], time.tm_mday, time.tm_hour, time.tm_min, time.tm_sec);
 		
-				writer.write_truncated(&hostNameWithoutDomain);
+				hostNameWithoutDomain.write(&mut writer);
 				writer.push(b' ');
 		
-				writer.write_truncated(&appName);
+				write_overridable(&mut writer, &appName, messageHeaderOverrides.appName, Rfc3164MaximumAppNameLength);
 				writer.push(b'[');
-				writer.write_truncated(&processId);
+				processId.write(&mut writer);
 				writer.push(b']');
 				writer.push(b' ');
 			},
@@ -84,16 +127,16 @@ impl SyslogRfc
 				let microseconds = time.tm_nsec / 1000;
 				write!(&mut writer, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z ", time.tm_year, time.tm_mon, time.tm_mday, time.tm_hour, time.tm_min, time.tm_sec, microseconds);
 	
-				writer.write_truncated(&hostName);
+				hostName.write(&mut writer);
 				writer.push(b' ');
 	
-				writer.write_truncated(&appName);
+				write_overridable(&mut writer, &appName, messageHeaderOverrides.appName, Rfc5424MaximumAppNameLength);
 				writer.push(b' ');
 	
-				writer.write_truncated(&processId);
+				processId.write(&mut writer);
 				writer.push(b' ');
 	
-				writer.write_truncated(&messageId);
+				write_overridable(&mut writer, &messageId, messageHeaderOverrides.messageId, Rfc5424MaximumMessageIdLength);
 				writer.push(b' ');
 	
 				write_structured_data_elements(&mut writer, structured_data_elements);
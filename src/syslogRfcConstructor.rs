@@ -6,6 +6,8 @@ extern crate process;
 use self::process::Process;
 use rfc5424::TruncatedUsAsciiPrintableString;
 use SyslogRfc;
+use ForkAwareProcessId;
+use HostName;
 
 
 const NILVALUE: &'static str = "-";
@@ -19,7 +21,19 @@ pub enum SyslogRfcConstructor
 
 impl SyslogRfcConstructor
 {
+	/// HOSTNAME is `process.hostNameWithoutDomain` (RFC 3164) or `process.hostName` (RFC 5424), verbatim, and is never refreshed
 	pub fn new(&self, process: &Process, messageId: &str) -> SyslogRfc
+	{
+		let hostName = match *self
+		{
+			SyslogRfcConstructor::Rfc3164 => HostName::fixed(&process.hostNameWithoutDomain),
+			SyslogRfcConstructor::Rfc5424 => HostName::fixed(&process.hostName),
+		};
+		self.newWithHostName(process, messageId, hostName)
+	}
+	
+	/// eg `HostName::new(HostNameStrategy::ShortName, Some(Duration::from_secs(60)))` for RFC 3164
+	pub fn newWithHostName(&self, process: &Process, messageId: &str, hostName: HostName) -> SyslogRfc
 	{
 		let programName = &process.programName;
 		let truncatedPrintableUsAsciiProgramName = TruncatedUsAsciiPrintableString::new(if programName.is_empty()
@@ -31,46 +45,26 @@ impl SyslogRfcConstructor
 			programName
 		}, *self as usize);
 		
-		let truncatedPrintableProcessId = TruncatedUsAsciiPrintableString::new(&process.pid.to_string(), 128);
+		let processId = ForkAwareProcessId::new(&process.pid.to_string());
 		
 		match *self
 		{
 			SyslogRfcConstructor::Rfc3164 =>
 			{
-				let hostNameWithoutDomain = &process.hostNameWithoutDomain;
-				let truncatedPrintableUsAsciiHostNameWithoutDomain = TruncatedUsAsciiPrintableString::new(if hostNameWithoutDomain.is_empty()
-				{
-					NILVALUE
-				}
-				else
-				{
-					hostNameWithoutDomain
-				}, 255);
-				
 				SyslogRfc::Rfc3164
 				{
-					hostNameWithoutDomain: truncatedPrintableUsAsciiHostNameWithoutDomain,
+					hostNameWithoutDomain: hostName,
 					appName: truncatedPrintableUsAsciiProgramName,
-					processId: truncatedPrintableProcessId,
+					processId: processId,
 				}
 			},
 			SyslogRfcConstructor::Rfc5424 =>
 			{
-				let hostName = &process.hostName;
-				let truncatedPrintableUsAsciiHostName = TruncatedUsAsciiPrintableString::new(if hostName.is_empty()
-				{
-					NILVALUE
-				}
-				else
-				{
-					hostName
-				}, 255);
-				
 				SyslogRfc::Rfc5424
 				{
-					hostName: truncatedPrintableUsAsciiHostName,
+					hostName: hostName,
 					appName: truncatedPrintableUsAsciiProgramName,
-					processId: truncatedPrintableProcessId,
+					processId: processId,
 					messageId: TruncatedUsAsciiPrintableString::new(if messageId.is_empty()
 					{
 						NILVALUE
@@ -83,5 +77,4 @@ impl SyslogRfcConstructor
 			},
 		}
 	}
-	
-}
\ No newline at end of file
+}
@@ -0,0 +1,86 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use syslogSenders::Rfc3164Facility;
+use rfc5424::StructuredData;
+use Severity;
+use MessageHeaderOverrides;
+
+
+/// A record of one call to `SyslogSender::send()`, as captured by `CapturingSyslogSender`
+#[derive(Debug, Clone)]
+pub struct CapturedMessage
+{
+	pub rfc3164Facility: Rfc3164Facility,
+	pub severity: Severity,
+	/// Only present if overridden for this message
+	pub appName: Option<String>,
+	/// Only present if overridden for this message
+	pub messageId: Option<String>,
+	/// Pairs of SD-ID and (PARAM-NAME, PARAM-VALUE) pairs
+	pub structuredData: Vec<(String, Vec<(String, String)>)>,
+	pub message: String,
+	/// As rendered by the sender's `SyslogRfc` using its frozen time
+	pub rendered: Vec<u8>,
+}
+
+impl CapturedMessage
+{
+	pub fn new(rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str, rendered: Vec<u8>) -> CapturedMessage
+	{
+		let structuredData = structured_data_elements.iter().map(|structured_data_element|
+		{
+			let parameters = structured_data_element.parameters().iter().map(|parameter| (parameter.name().as_str().to_owned(), parameter.value().to_owned())).collect();
+			(structured_data_element.id().as_str().to_owned(), parameters)
+		}).collect();
+		
+		CapturedMessage
+		{
+			rfc3164Facility: rfc3164Facility,
+			severity: severity,
+			appName: messageHeaderOverrides.appName.map(|appName| appName.to_owned()),
+			messageId: messageHeaderOverrides.messageId.map(|messageId| messageId.to_owned()),
+			structuredData: structuredData,
+			message: message.to_owned(),
+			rendered: rendered,
+		}
+	}
+	
+	pub fn hasStructuredDataElement(&self, sdId: &str) -> bool
+	{
+		self.structuredData.iter().any(|&(ref id, _)| id == sdId)
+	}
+	
+	/// Returns the value of the first parameter called `name` in the element `sdId`
+	pub fn structuredDataParameter(&self, sdId: &str, name: &str) -> Option<&str>
+	{
+		self.structuredDataParameters(sdId, name).into_iter().next()
+	}
+	
+	/// Returns all the values of parameters called `name` in the element `sdId`, as parameters may repeat (eg origin's `ip`)
+	pub fn structuredDataParameters(&self, sdId: &str, name: &str) -> Vec<&str>
+	{
+		let mut values = Vec::new();
+		for &(ref id, ref parameters) in &self.structuredData
+		{
+			if id != sdId
+			{
+				continue;
+			}
+			for &(ref parameterName, ref parameterValue) in parameters
+			{
+				if parameterName == name
+				{
+					values.push(&parameterValue[..]);
+				}
+			}
+		}
+		values
+	}
+	
+	/// Lossy, as RFC 3164 output need not be UTF-8
+	pub fn renderedAsString(&self) -> String
+	{
+		String::from_utf8_lossy(&self.rendered).into_owned()
+	}
+}
@@ -0,0 +1,194 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate time;
+use std::io::Result;
+use std::sync::Mutex;
+use self::time::Tm;
+use self::time::Timespec;
+use syslogSenders::CapturedMessage;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetrics;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use rfc5424::StructuredData;
+use Severity;
+use SyslogRfc;
+use MessageHeaderOverrides;
+
+
+/// Records every message sent rather than sending it, so that code which logs can be tested deterministically
+/// Messages are also rendered using `SyslogRfc` with a frozen clock, so that output can be compared against golden snapshots
+#[derive(Debug)]
+pub struct CapturingSyslogSender
+{
+	syslogRfc: SyslogRfc,
+	frozenTime: Tm,
+	capturedMessages: Mutex<Vec<CapturedMessage>>,
+	metrics: SyslogSenderMetrics,
+}
+
+impl CapturingSyslogSender
+{
+	pub fn new(syslogRfc: SyslogRfc, frozenTime: Tm) -> CapturingSyslogSender
+	{
+		CapturingSyslogSender
+		{
+			syslogRfc: syslogRfc,
+			frozenTime: frozenTime,
+			capturedMessages: Mutex::new(Vec::new()),
+			metrics: SyslogSenderMetrics::new(),
+		}
+	}
+	
+	/// Time is frozen at the Unix epoch
+	pub fn new_at_epoch(syslogRfc: SyslogRfc) -> CapturingSyslogSender
+	{
+		CapturingSyslogSender::new(syslogRfc, time::at_utc(Timespec::new(0, 0)))
+	}
+	
+	/// RFC 5424 from `myprogram` (PROCID 5) on `macpro.example.com`, at the Unix epoch; for this crate's own tests of other senders
+	#[cfg(test)]
+	pub fn forTesting() -> CapturingSyslogSender
+	{
+		use rfc5424::TruncatedUsAsciiPrintableString;
+		use ForkAwareProcessId;
+		use HostName;
+		
+		CapturingSyslogSender::new_at_epoch(SyslogRfc::Rfc5424
+		{
+			hostName: HostName::fixed("macpro.example.com"),
+			appName: TruncatedUsAsciiPrintableString::new("myprogram", 48),
+			processId: ForkAwareProcessId::new("5"),
+			messageId: TruncatedUsAsciiPrintableString::new("-", 32),
+		})
+	}
+	
+	pub fn messages(&self) -> Vec<CapturedMessage>
+	{
+		self.capturedMessages.lock().unwrap().clone()
+	}
+	
+	pub fn len(&self) -> usize
+	{
+		self.capturedMessages.lock().unwrap().len()
+	}
+	
+	pub fn is_empty(&self) -> bool
+	{
+		self.len() == 0
+	}
+	
+	pub fn clear(&self)
+	{
+		self.capturedMessages.lock().unwrap().clear()
+	}
+	
+	/// Returns the first message logged with `severity` whose message contains `contains`
+	pub fn find_logged(&self, severity: Severity, contains: &str) -> Option<CapturedMessage>
+	{
+		self.capturedMessages.lock().unwrap().iter().find(|capturedMessage| capturedMessage.severity == severity && capturedMessage.message.contains(contains)).cloned()
+	}
+	
+	/// Panics, listing everything captured, unless a message was logged with `severity` whose message contains `contains`
+	/// Returns the matching message so that its structured data can be checked
+	pub fn assert_logged(&self, severity: Severity, contains: &str) -> CapturedMessage
+	{
+		match self.find_logged(severity, contains)
+		{
+			Some(capturedMessage) => capturedMessage,
+			None => panic!("No message with severity {:?} containing {:?} was logged; captured:\n{}", severity, contains, self.snapshot()),
+		}
+	}
+	
+	/// Panics, listing everything captured, if a message was logged with `severity` whose message contains `contains`
+	pub fn assert_not_logged(&self, severity: Severity, contains: &str)
+	{
+		if self.find_logged(severity, contains).is_some()
+		{
+			panic!("A message with severity {:?} containing {:?} was logged; captured:\n{}", severity, contains, self.snapshot());
+		}
+	}
+	
+	/// Returns the first value of the parameter `name` in the element `sdId` in any captured message
+	pub fn structuredDataParameter(&self, sdId: &str, name: &str) -> Option<String>
+	{
+		for capturedMessage in self.capturedMessages.lock().unwrap().iter()
+		{
+			if let Some(value) = capturedMessage.structuredDataParameter(sdId, name)
+			{
+				return Some(value.to_owned());
+			}
+		}
+		None
+	}
+	
+	/// All captured messages as rendered, one per line, suitable for comparison with a golden file
+	pub fn snapshot(&self) -> String
+	{
+		let mut snapshot = String::new();
+		for capturedMessage in self.capturedMessages.lock().unwrap().iter()
+		{
+			snapshot.push_str(&capturedMessage.renderedAsString());
+			snapshot.push('\n');
+		}
+		snapshot
+	}
+}
+
+impl SyslogSender for CapturingSyslogSender
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		let rendered = self.syslogRfc.write(self.frozenTime, rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message);
+		if self.syslogRfc.truncates(messageHeaderOverrides)
+		{
+			self.metrics.recordTruncation();
+		}
+		
+		self.metrics.recordSent(rendered.len());
+		
+		let capturedMessage = CapturedMessage::new(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message, rendered);
+		self.capturedMessages.lock().unwrap().push(capturedMessage);
+		Ok(())
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		Some(self.metrics.snapshot())
+	}
+}
+
+#[test]
+fn capturesMessagesAndStructuredData()
+{
+	use rfc5424::StructuredDataElement;
+	use rfc5424::TruncatedUsAsciiPrintableString;
+	use ForkAwareProcessId;
+	use HostName;
+	
+	let syslogRfc = SyslogRfc::Rfc5424
+	{
+		hostName: HostName::fixed("macpro.example.com"),
+		appName: TruncatedUsAsciiPrintableString::new("myprogram", 48),
+		processId: ForkAwareProcessId::new("5"),
+		messageId: TruncatedUsAsciiPrintableString::new("-", 32),
+	};
+	let capturingSyslogSender = CapturingSyslogSender::new_at_epoch(syslogRfc);
+	
+	let mut structuredData = StructuredData::new();
+	structuredData.insert(StructuredDataElement::timeQuality(true, true, Some(1000))).unwrap();
+	capturingSyslogSender.send(Rfc3164Facility::local0, Severity::LOG_ERR, &structuredData, "Hello World").unwrap();
+	
+	let capturedMessage = capturingSyslogSender.assert_logged(Severity::LOG_ERR, "World");
+	assert_eq!(capturedMessage.structuredDataParameter("timeQuality", "tzKnown"), Some("1"));
+	assert_eq!(capturedMessage.structuredDataParameter("timeQuality", "syncAccuracy"), Some("1000"));
+	capturingSyslogSender.assert_not_logged(Severity::LOG_INFO, "World");
+	assert_eq!(capturingSyslogSender.len(), 1);
+}
@@ -0,0 +1,197 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate time;
+extern crate libc;
+use std::env::var_os;
+use std::io::Result;
+use std::io::Write;
+use std::io::stderr;
+use std::time::Instant;
+use self::time::Tm;
+use self::libc::isatty;
+use self::libc::STDERR_FILENO;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetrics;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use rfc5424::StructuredData;
+use Severity;
+use MessageHeaderOverrides;
+use VecU8PushStr;
+
+
+const NILVALUE: &'static str = "-";
+
+const AnsiReset: &'static str = "\x1B[0m";
+
+/// Writes human-readable lines to standard error; intended for local development rather than as a transport
+/// Lines are of the form `2016-05-08T14:23:19.854377Z err     myprogram msgid origin.ip=10.0.0.1 message`
+#[derive(Debug)]
+pub struct ConsoleSyslogSender
+{
+	appName: String,
+	messageId: String,
+	useColour: bool,
+	metrics: SyslogSenderMetrics,
+}
+
+impl ConsoleSyslogSender
+{
+	/// Colour is used only if standard error is a terminal and `NO_COLOR` is not set
+	pub fn new(appName: &str, messageId: &str) -> ConsoleSyslogSender
+	{
+		ConsoleSyslogSender::new_with_colour(appName, messageId, ConsoleSyslogSender::shouldUseColour())
+	}
+	
+	pub fn new_with_colour(appName: &str, messageId: &str, useColour: bool) -> ConsoleSyslogSender
+	{
+		ConsoleSyslogSender
+		{
+			appName: if appName.is_empty() { NILVALUE.to_owned() } else { appName.to_owned() },
+			messageId: if messageId.is_empty() { NILVALUE.to_owned() } else { messageId.to_owned() },
+			useColour: useColour,
+			metrics: SyslogSenderMetrics::new(),
+		}
+	}
+	
+	/// See http://no-color.org/; any value of `NO_COLOR`, even an empty one, disables colour
+	pub fn shouldUseColour() -> bool
+	{
+		if var_os("NO_COLOR").is_some()
+		{
+			return false;
+		}
+		
+		unsafe { isatty(STDERR_FILENO) == 1 }
+	}
+	
+	fn write(&self, time: Tm, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Vec<u8>
+	{
+		let mut writer: Vec<u8> = Vec::with_capacity(256 + message.len());
+		
+		let microseconds = time.tm_nsec / 1000;
+		write!(&mut writer, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:06}Z ", time.tm_year + 1900, time.tm_mon + 1, time.tm_mday, time.tm_hour, time.tm_min, time.tm_sec, microseconds).unwrap();
+		
+		let name = severity.name();
+		let colour = ConsoleSyslogSender::colour(severity);
+		if self.useColour
+		{
+			writer.push_str(colour);
+			write!(&mut writer, "{:<7}", name).unwrap();
+			writer.push_str(AnsiReset);
+		}
+		else
+		{
+			write!(&mut writer, "{:<7}", name).unwrap();
+		}
+		writer.push(b' ');
+		
+		match messageHeaderOverrides.appName
+		{
+			None => writer.push_str(&self.appName),
+			Some("") => writer.push_str(NILVALUE),
+			Some(appName) => writer.push_str(appName),
+		}
+		writer.push(b' ');
+		
+		match messageHeaderOverrides.messageId
+		{
+			None => writer.push_str(&self.messageId),
+			Some("") => writer.push_str(NILVALUE),
+			Some(messageId) => writer.push_str(messageId),
+		}
+		writer.push(b' ');
+		
+		for structured_data_element in structured_data_elements
+		{
+			let id = structured_data_element.id().as_str();
+			for parameter in structured_data_element.parameters()
+			{
+				let value = parameter.value();
+				if value.is_empty() || value.contains(' ')
+				{
+					write!(&mut writer, "{}.{}={:?} ", id, parameter.name().as_str(), value).unwrap();
+				}
+				else
+				{
+					write!(&mut writer, "{}.{}={} ", id, parameter.name().as_str(), value).unwrap();
+				}
+			}
+		}
+		
+		writer.push_str(message);
+		writer.push(b'\n');
+		
+		writer
+	}
+	
+	fn colour(severity: Severity) -> &'static str
+	{
+		match severity
+		{
+			Severity::LOG_EMERG => "\x1B[1;31m",
+			Severity::LOG_ALERT => "\x1B[1;31m",
+			Severity::LOG_CRIT => "\x1B[1;31m",
+			Severity::LOG_ERR => "\x1B[31m",
+			Severity::LOG_WARNING => "\x1B[33m",
+			Severity::LOG_NOTICE => "\x1B[36m",
+			Severity::LOG_INFO => "\x1B[32m",
+			Severity::LOG_DEBUG => "\x1B[2m",
+		}
+	}
+}
+
+impl SyslogSender for ConsoleSyslogSender
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	#[allow(unused_variables)]
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		let timeNow = time::now_utc();
+		
+		let data = self.write(timeNow, severity, messageHeaderOverrides, structured_data_elements, message);
+		
+		// A single write_all() of the whole line, so that lines from different threads do not interleave
+		let startedAt = Instant::now();
+		let standardError = stderr();
+		let result = standardError.lock().write_all(&data);
+		self.metrics.record(data.len(), startedAt, &result);
+		result
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		Some(self.metrics.snapshot())
+	}
+}
+
+#[test]
+fn coloursSeverityAndQuotesStructuredData()
+{
+	use rfc5424::OwnedStructuredDataElement;
+	
+	let time = Tm { tm_sec: 19, tm_min: 23, tm_hour: 14, tm_mday: 8, tm_mon: 4, tm_year: 116, tm_wday: 0, tm_yday: 128, tm_isdst: 0, tm_utcoff: 0, tm_nsec: 854377000 };
+	let element = OwnedStructuredDataElement::new("req@38188").param("plain", "abc").param("spaced", "say \"hi\" now").param("empty", "");
+	let mut structuredData = StructuredData::new();
+	structuredData.insert(element.borrow()).unwrap();
+	
+	let consoleSyslogSender = ConsoleSyslogSender::new_with_colour("myprogram", "", false);
+	let written = consoleSyslogSender.write(time, Severity::LOG_ERR, &MessageHeaderOverrides::default(), &structuredData, "Hello World");
+	assert_eq!(String::from_utf8(written).unwrap(), "2016-05-08T14:23:19.854377Z err     myprogram - req@38188.plain=abc req@38188.spaced=\"say \\\"hi\\\" now\" req@38188.empty=\"\" Hello World\n");
+	
+	let consoleSyslogSender = ConsoleSyslogSender::new_with_colour("myprogram", "msgid", true);
+	for severity in Severity::iter()
+	{
+		let written = String::from_utf8(consoleSyslogSender.write(time, severity, &MessageHeaderOverrides::default(), &StructuredData::new(), "Hello World")).unwrap();
+		let expected = format!("2016-05-08T14:23:19.854377Z {}{:<7}{} myprogram msgid Hello World\n", ConsoleSyslogSender::colour(severity), severity.name(), AnsiReset);
+		assert_eq!(written, expected);
+	}
+	assert_eq!(ConsoleSyslogSender::colour(Severity::LOG_WARNING), "\x1B[33m");
+	assert_ne!(ConsoleSyslogSender::colour(Severity::LOG_ERR), ConsoleSyslogSender::colour(Severity::LOG_INFO));
+}
@@ -0,0 +1,183 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+
+// Bug in rust nightly as of May 5th
+#![allow(dead_code)]
+
+extern crate time;
+extern crate network_constants;
+use std::io::Result;
+use std::io::ErrorKind;
+use std::net::ToSocketAddrs;
+use std::net::Ipv4Addr;
+use std::net::Ipv6Addr;
+use std::net::TcpStream;
+use std::io::Write;
+use std::net::Shutdown;
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+use self::network_constants::tcp::SyslogPort;
+use self::network_constants::ipv4;
+use self::network_constants::ipv6;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetrics;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use Severity;
+use SyslogRfc;
+use MessageHeaderOverrides;
+use rfc5424::StructuredData;
+use fork_generation;
+
+/// Sends are serialised on the stream, so this can be shared between threads
+/// After a `fork()`, the child opens its own connection before its first send, as writes to a shared stream would interleave with the parent's
+#[derive(Debug)]
+pub struct InsecureBlockingTcpSyslogSender
+{
+	syslog2Rfc: SyslogRfc,
+	stream: Mutex<TcpStream>,
+	forkGeneration: AtomicUsize,
+	serverSocketAddresses: Vec<SocketAddr>,
+	metrics: SyslogSenderMetrics,
+}
+
+/// Formerly not safe to share between threads
+#[deprecated(note = "use InsecureBlockingTcpSyslogSender, which is the same type")]
+pub type InsecureThreadUnsafeBlockingTcpSyslogSender = InsecureBlockingTcpSyslogSender;
+
+impl InsecureBlockingTcpSyslogSender
+{
+	fn new<S: ToSocketAddrs>(syslog2Rfc: SyslogRfc, serverSocketAddress: S) -> Result<InsecureBlockingTcpSyslogSender>
+	{
+		let forkGeneration = fork_generation();
+		let serverSocketAddresses: Vec<SocketAddr> = try!(serverSocketAddress.to_socket_addrs()).collect();
+		let stream = try!(InsecureBlockingTcpSyslogSender::connect(&serverSocketAddresses));
+		
+		Ok(InsecureBlockingTcpSyslogSender
+		{
+			syslog2Rfc: syslog2Rfc,
+			stream: Mutex::new(stream),
+			forkGeneration: AtomicUsize::new(forkGeneration),
+			serverSocketAddresses: serverSocketAddresses,
+			metrics: SyslogSenderMetrics::new(),
+		})
+	}
+	
+	fn connect(serverSocketAddresses: &[SocketAddr]) -> Result<TcpStream>
+	{
+		let stream = try!(TcpStream::connect(serverSocketAddresses));
+		try!(stream.set_write_timeout(None));
+		try!(stream.shutdown(Shutdown::Read));
+		Ok(stream)
+	}
+	
+	/// The inherited stream is dropped, not shut down, so that the parent's connection is unaffected
+	/// If connecting fails, the next send tries again
+	fn reconnectIfForked(&self, stream: &mut TcpStream) -> Result<()>
+	{
+		let forkGeneration = fork_generation();
+		if self.forkGeneration.load(Ordering::Relaxed) == forkGeneration
+		{
+			return Ok(());
+		}
+		
+		*stream = try!(InsecureBlockingTcpSyslogSender::connect(&self.serverSocketAddresses));
+		self.forkGeneration.store(forkGeneration, Ordering::Relaxed);
+		Ok(())
+	}
+	
+	fn new_ipv4_localhost_514(syslog2Rfc: SyslogRfc) -> Result<InsecureBlockingTcpSyslogSender>
+	{
+		InsecureBlockingTcpSyslogSender::new(syslog2Rfc, (ipv4::localhost(), SyslogPort))
+	}
+	
+	fn new_ipv4_514(syslog2Rfc: SyslogRfc, serverAddress: Ipv4Addr) -> Result<InsecureBlockingTcpSyslogSender>
+	{
+		InsecureBlockingTcpSyslogSender::new(syslog2Rfc, (serverAddress, SyslogPort))
+	}
+	
+	fn new_ipv6_localhost_514(syslog2Rfc: SyslogRfc) -> Result<InsecureBlockingTcpSyslogSender>
+	{
+		InsecureBlockingTcpSyslogSender::new(syslog2Rfc, (ipv6::localhost(), SyslogPort))
+	}
+	
+	fn new_ipv6_514(syslog2Rfc: SyslogRfc, serverAddress: Ipv6Addr) -> Result<InsecureBlockingTcpSyslogSender>
+	{
+		InsecureBlockingTcpSyslogSender::new(syslog2Rfc, (serverAddress, SyslogPort))
+	}
+}
+
+impl InsecureBlockingTcpSyslogSender
+{
+	fn sendData(&self, data: &[u8]) -> Result<()>
+	{
+		let mut stream = self.stream.lock().unwrap();
+		try!(self.reconnectIfForked(&mut stream));
+		
+		let bytesLength: usize = data.len();
+		let mut bytesWrittenSoFar: usize = 0;
+		
+		loop
+		{
+			let result = stream.write(&data[bytesWrittenSoFar..]);
+		
+			match result
+			{
+				Ok(bytesSent) =>
+				{
+					bytesWrittenSoFar += bytesSent;
+					if bytesWrittenSoFar == bytesLength
+					{
+						return Ok(())
+					}
+					debug_assert!(bytesWrittenSoFar <= bytesLength, "Syscalls to UDP sendto() are broken - they overwrote!");
+				},
+				Err(error) =>
+				{
+					match error.kind()
+					{
+						ErrorKind::WriteZero => self.metrics.recordRetry(),
+						ErrorKind::WouldBlock => self.metrics.recordRetry(),
+						ErrorKind::TimedOut => self.metrics.recordRetry(),
+						ErrorKind::Interrupted => self.metrics.recordRetry(),
+						//ErrorKind::ConnectionAborted => ? reconnect ?
+						_ => return Err(error)
+					}
+				},
+			}
+		}
+	}
+}
+
+impl SyslogSender for InsecureBlockingTcpSyslogSender
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		let timeNow = time::now_utc();
+		let data = self.syslog2Rfc.write(timeNow, rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message);
+		if self.syslog2Rfc.truncates(messageHeaderOverrides)
+		{
+			self.metrics.recordTruncation();
+		}
+		
+		let startedAt = Instant::now();
+		let result = self.sendData(&data);
+		self.metrics.record(data.len(), startedAt, &result);
+		result
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		Some(self.metrics.snapshot())
+	}
+}
@@ -13,39 +13,76 @@ use std::net::ToSocketAddrs;
 use std::net::Ipv4Addr;
 use std::net::Ipv6Addr;
 use std::net::UdpSocket;
+use std::sync::RwLock;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
 use self::network_constants::UdpPort;
 use self::network_constants::udp::BindToAnyLocalUdpPortAvailable;
 use self::network_constants::udp::SyslogPort;
 use self::network_constants::ipv4;
 use self::network_constants::ipv6;
-use syslog2Senders::Rfc3164Facility;
-use syslog2Senders::SyslogSender;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetrics;
+use syslogSenders::SyslogSenderMetricsSnapshot;
 use rfc5424::StructuredData;
 use SyslogRfc;
+use MessageHeaderOverrides;
 use Severity;
+use fork_generation;
 
+/// After a `fork()`, the child binds its own socket before its first send
 #[derive(Debug)]
 pub struct InsecureBlockingUdpSyslogSender<S: ToSocketAddrs>
 {
 	syslog2Rfc: SyslogRfc,
-	socket: UdpSocket,
+	socket: RwLock<UdpSocket>,
+	forkGeneration: AtomicUsize,
+	localSocketAddress: S,
 	serverSocketAddress: S,
+	metrics: SyslogSenderMetrics,
 }
 
 impl <S: ToSocketAddrs> InsecureBlockingUdpSyslogSender<S>
 {
 	fn new(syslog2Rfc: SyslogRfc, localSocketAddress: S, serverSocketAddress: S) -> Result<InsecureBlockingUdpSyslogSender<S>>
 	{
-		let socket = try!(UdpSocket::bind(localSocketAddress));
-		try!(socket.set_write_timeout(None));
+		let forkGeneration = fork_generation();
+		let socket = try!(InsecureBlockingUdpSyslogSender::bind(&localSocketAddress));
 		
 		Ok(InsecureBlockingUdpSyslogSender
 		{
 			syslog2Rfc: syslog2Rfc,
-			socket: socket,
+			socket: RwLock::new(socket),
+			forkGeneration: AtomicUsize::new(forkGeneration),
+			localSocketAddress: localSocketAddress,
 			serverSocketAddress: serverSocketAddress,
+			metrics: SyslogSenderMetrics::new(),
 		})
 	}
+	
+	fn bind(localSocketAddress: &S) -> Result<UdpSocket>
+	{
+		let socket = try!(UdpSocket::bind(localSocketAddress));
+		try!(socket.set_write_timeout(None));
+		Ok(socket)
+	}
+	
+	/// If binding fails (eg because `localSocketAddress` has a fixed port the parent still holds), the inherited socket continues to be used; datagrams are never interleaved, so this is safe
+	fn rebindIfForked(&self)
+	{
+		let forkGeneration = fork_generation();
+		if self.forkGeneration.swap(forkGeneration, Ordering::Relaxed) == forkGeneration
+		{
+			return;
+		}
+		
+		if let Ok(socket) = InsecureBlockingUdpSyslogSender::bind(&self.localSocketAddress)
+		{
+			*self.socket.write().unwrap() = socket;
+		}
+	}
 }
 
 impl InsecureBlockingUdpSyslogSender<(Ipv4Addr, UdpPort)>
@@ -76,20 +113,19 @@ impl InsecureBlockingUdpSyslogSender<(Ipv6Addr, UdpPort)>
 	}
 }
 
-impl <S: ToSocketAddrs> SyslogSender for InsecureBlockingUdpSyslogSender<S>
+impl <S: ToSocketAddrs> InsecureBlockingUdpSyslogSender<S>
 {
-	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	fn sendData(&self, data: &[u8]) -> Result<()>
 	{
-		let timeNow = time::now_utc();
-		
-		let data = self.syslog2Rfc.write(timeNow, rfc3164Facility, severity, structured_data_elements, message);
+		self.rebindIfForked();
 		
 		let bytesLength: usize = data.len();
 		let mut bytesWrittenSoFar: usize = 0;
 		
+		let socket = self.socket.read().unwrap();
 		loop
 		{
-			let result = self.socket.send_to(&data[bytesWrittenSoFar..], &self.serverSocketAddress);
+			let result = socket.send_to(&data[bytesWrittenSoFar..], &self.serverSocketAddress);
 		
 			match result
 			{
@@ -106,10 +142,10 @@ impl <S: ToSocketAddrs> SyslogSender for InsecureBlockingUdpSyslogSender<S>
 				{
 					match error.kind()
 					{
-						ErrorKind::WriteZero => continue, // Hmmm, is this possible for UDP?
-						ErrorKind::WouldBlock => continue,
-						ErrorKind::TimedOut => continue,
-						ErrorKind::Interrupted => continue,
+						ErrorKind::WriteZero => self.metrics.recordRetry(), // Hmmm, is this possible for UDP?
+						ErrorKind::WouldBlock => self.metrics.recordRetry(),
+						ErrorKind::TimedOut => self.metrics.recordRetry(),
+						ErrorKind::Interrupted => self.metrics.recordRetry(),
 						//ErrorKind::ConnectionAborted => ? reconnect - but this is UDP ...
 						_ => return Err(error)
 					}
@@ -118,3 +154,33 @@ impl <S: ToSocketAddrs> SyslogSender for InsecureBlockingUdpSyslogSender<S>
 		}
 	}
 }
+
+impl <S: ToSocketAddrs> SyslogSender for InsecureBlockingUdpSyslogSender<S>
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		let timeNow = time::now_utc();
+		
+		let data = self.syslog2Rfc.write(timeNow, rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message);
+		if self.syslog2Rfc.truncates(messageHeaderOverrides)
+		{
+			self.metrics.recordTruncation();
+		}
+		
+		let startedAt = Instant::now();
+		let result = self.sendData(&data);
+		self.metrics.record(data.len(), startedAt, &result);
+		result
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		Some(self.metrics.snapshot())
+	}
+}
@@ -0,0 +1,107 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::io::Result;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use rfc5424::StructuredData;
+use LogMask;
+use Severity;
+use MessageHeaderOverrides;
+
+
+/// Wraps a `SyslogSender` so that messages with a severity not contained in a `LogMask` are dropped, as libc's `syslog()` does after `setlogmask()`
+/// This allows the same mask policy to apply to every transport; the mask can be changed at runtime from any thread
+#[derive(Debug)]
+pub struct MaskingSyslogSender<S: SyslogSender>
+{
+	sender: S,
+	mask: AtomicUsize,
+	dropped: AtomicUsize,
+}
+
+impl <S: SyslogSender> MaskingSyslogSender<S>
+{
+	pub fn new(sender: S, mask: LogMask) -> MaskingSyslogSender<S>
+	{
+		MaskingSyslogSender
+		{
+			sender: sender,
+			mask: AtomicUsize::new(mask.bits() as usize),
+			dropped: AtomicUsize::new(0),
+		}
+	}
+	
+	#[inline(always)]
+	pub fn mask(&self) -> LogMask
+	{
+		LogMask::from_bits_truncate(self.mask.load(Ordering::Relaxed) as i32)
+	}
+	
+	/// Atomically replaces the mask, and returns the old mask
+	#[inline(always)]
+	pub fn setMask(&self, mask: LogMask) -> LogMask
+	{
+		LogMask::from_bits_truncate(self.mask.swap(mask.bits() as usize, Ordering::Relaxed) as i32)
+	}
+	
+	/// Total number of messages dropped since creation
+	pub fn dropped(&self) -> usize
+	{
+		self.dropped.load(Ordering::Relaxed)
+	}
+}
+
+impl <S: SyslogSender> SyslogSender for MaskingSyslogSender<S>
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		if !self.mask().contains(severity)
+		{
+			self.dropped.fetch_add(1, Ordering::Relaxed);
+			return Ok(());
+		}
+		
+		self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message)
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		Some(self.sender.metrics().unwrap_or_default().withAdditionalDrops(self.dropped()))
+	}
+}
+
+#[test]
+fn dropsMaskedSeveritiesAndCanBeChangedAtRuntime()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let maskingSyslogSender = MaskingSyslogSender::new(CapturingSyslogSender::forTesting(), LogMask::upto(Severity::LOG_WARNING));
+	let noStructuredData = StructuredData::new();
+	
+	maskingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_INFO, &noStructuredData, "info before").unwrap();
+	maskingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_ERR, &noStructuredData, "error before").unwrap();
+	assert_eq!(maskingSyslogSender.dropped(), 1);
+	
+	assert_eq!(maskingSyslogSender.setMask(LogMask::upto(Severity::LOG_INFO)), LogMask::upto(Severity::LOG_WARNING));
+	assert_eq!(maskingSyslogSender.mask(), LogMask::upto(Severity::LOG_INFO));
+	maskingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_INFO, &noStructuredData, "info after").unwrap();
+	maskingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_DEBUG, &noStructuredData, "debug after").unwrap();
+	assert_eq!(maskingSyslogSender.dropped(), 2);
+	
+	let sent: Vec<String> = maskingSyslogSender.sender.messages().into_iter().map(|capturedMessage| capturedMessage.message).collect();
+	assert_eq!(sent, vec!["error before", "info after"]);
+	
+	let metrics = maskingSyslogSender.metrics().unwrap();
+	assert_eq!(metrics.drops, 2);
+	assert_eq!(metrics.messagesSent, 2);
+}
@@ -0,0 +1,84 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::io::Result;
+use std::time::Instant;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetrics;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use rfc5424::StructuredData;
+use Severity;
+use MessageHeaderOverrides;
+
+
+/// Wraps any `SyslogSender`, such as one from another crate, to record metrics for it
+/// Byte, retry and truncation counts are those of the wrapped sender if it keeps metrics, as only it knows what it actually wrote
+/// Otherwise, byte counts are of the message body only, and retries and truncations are not counted
+#[derive(Debug)]
+pub struct MeasuringSyslogSender<S: SyslogSender>
+{
+	sender: S,
+	metrics: SyslogSenderMetrics,
+}
+
+impl <S: SyslogSender> MeasuringSyslogSender<S>
+{
+	pub fn new(sender: S) -> MeasuringSyslogSender<S>
+	{
+		MeasuringSyslogSender
+		{
+			sender: sender,
+			metrics: SyslogSenderMetrics::new(),
+		}
+	}
+}
+
+impl <S: SyslogSender> SyslogSender for MeasuringSyslogSender<S>
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		let startedAt = Instant::now();
+		let result = self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message);
+		self.metrics.record(message.len(), startedAt, &result);
+		result
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		let mut snapshot = self.metrics.snapshot();
+		if let Some(wrapped) = self.sender.metrics()
+		{
+			snapshot.bytesSent = wrapped.bytesSent;
+			snapshot.retries = wrapped.retries;
+			snapshot.truncations = wrapped.truncations;
+			snapshot.drops += wrapped.drops;
+		}
+		Some(snapshot)
+	}
+}
+
+#[test]
+fn measuresBytesActuallyWrittenByTheWrappedSender()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let measuringSyslogSender = MeasuringSyslogSender::new(CapturingSyslogSender::forTesting());
+	measuringSyslogSender.send(Rfc3164Facility::user, Severity::LOG_INFO, &StructuredData::new(), "Hello World").unwrap();
+	measuringSyslogSender.sendWithOverrides(Rfc3164Facility::user, Severity::LOG_INFO, &MessageHeaderOverrides::messageId("a-message-id-which-is-longer-than-32-characters"), &StructuredData::new(), "Hello").unwrap();
+	
+	let rendered: usize = measuringSyslogSender.sender.messages().iter().map(|capturedMessage| capturedMessage.rendered.len()).sum();
+	let snapshot = measuringSyslogSender.metrics().unwrap();
+	assert_eq!(snapshot.messagesSent, 2);
+	assert_eq!(snapshot.bytesSent, rendered);
+	assert!(snapshot.bytesSent > "Hello World".len() + "Hello".len());
+	assert_eq!(snapshot.truncations, 1);
+	assert_eq!(snapshot.sendErrorsTotal(), 0);
+	assert_eq!(snapshot.sendLatencyHistogram.iter().sum::<usize>(), 2);
+}
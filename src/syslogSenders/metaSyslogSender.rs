@@ -0,0 +1,119 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate uptime;
+use self::uptime::uptime_in_microseconds;
+use std::cmp::max;
+use std::io::Result;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use rfc5424::SequenceIdCounter;
+use rfc5424::StructuredData;
+use rfc5424::StructuredDataElement;
+use Severity;
+use MessageHeaderOverrides;
+
+
+/// Wraps a `SyslogSender` so that every message has a `meta` element (RFC 5424 section 7.3) with a `sequenceId` counted for just this sender, so that receivers can detect loss per stream
+/// Messages which already have a `meta` element are passed through unchanged, and do not use a `sequenceId`
+/// This must be the innermost wrapper, around the transport, as a `sequenceId` is used as soon as a message reaches it; a message dropped by a `MaskingSyslogSender`, `SamplingSyslogSender`, `RateLimitingSyslogSender` or `RepeatSuppressingSyslogSender` inside it would leave a gap that receivers would take for loss
+#[derive(Debug)]
+pub struct MetaSyslogSender<S: SyslogSender>
+{
+	sender: S,
+	sequenceIdCounter: SequenceIdCounter,
+	includeSysUpTime: bool,
+	language: Option<String>,
+}
+
+impl <S: SyslogSender> MetaSyslogSender<S>
+{
+	/// `language` is a language tag (RFC 4646) describing the MSG, eg `en-GB`; it is omitted if None
+	pub fn new(sender: S, includeSysUpTime: bool, language: Option<&str>) -> MetaSyslogSender<S>
+	{
+		MetaSyslogSender
+		{
+			sender: sender,
+			sequenceIdCounter: SequenceIdCounter::new(),
+			includeSysUpTime: includeSysUpTime,
+			language: language.map(|language| language.to_owned()),
+		}
+	}
+	
+	/// Hundredths of a second, as for the SNMP sysUpTime; None if the system uptime can not be read
+	fn sysUpTime(&self) -> Option<u64>
+	{
+		if !self.includeSysUpTime
+		{
+			return None;
+		}
+		
+		match uptime_in_microseconds()
+		{
+			Err(_) => None,
+			Ok(uptimeInMicroseconds) => Some((max(uptimeInMicroseconds, 0) / 10_000) as u64),
+		}
+	}
+}
+
+impl <S: SyslogSender> SyslogSender for MetaSyslogSender<S>
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		if structured_data_elements.contains("meta")
+		{
+			return self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message);
+		}
+		
+		let language = self.language.as_ref().map(|language| language.as_str());
+		let mut annotated = structured_data_elements.clone();
+		try!(annotated.insert(StructuredDataElement::meta(Some(self.sequenceIdCounter.next()), self.sysUpTime(), language)));
+		self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, &annotated, message)
+	}
+	
+	#[inline(always)]
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		self.sender.metrics()
+	}
+}
+
+#[test]
+fn sequenceIdsHaveNoGapsWhenInnermost()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	// Stands in for a MaskingSyslogSender, whose wrapped sender is private to its module
+	struct DropsDebug<S: SyslogSender>(S);
+	
+	impl <S: SyslogSender> SyslogSender for DropsDebug<S>
+	{
+		fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+		{
+			match severity
+			{
+				Severity::LOG_DEBUG => Ok(()),
+				_ => self.0.send(rfc3164Facility, severity, structured_data_elements, message),
+			}
+		}
+	}
+	
+	let sender = DropsDebug(MetaSyslogSender::new(CapturingSyslogSender::forTesting(), false, Some("en-GB")));
+	for severity in vec![Severity::LOG_INFO, Severity::LOG_DEBUG, Severity::LOG_DEBUG, Severity::LOG_ERR]
+	{
+		sender.send(Rfc3164Facility::user, severity, &StructuredData::new(), "hello").unwrap();
+	}
+	
+	let capturedMessages = sender.0.sender.messages();
+	let sequenceIds: Vec<&str> = capturedMessages.iter().map(|capturedMessage| capturedMessage.structuredDataParameter("meta", "sequenceId").unwrap()).collect();
+	assert_eq!(sequenceIds, vec!["1", "2"]);
+	assert_eq!(capturedMessages[0].structuredDataParameter("meta", "language"), Some("en-GB"));
+	assert_eq!(capturedMessages[0].structuredDataParameter("meta", "sysUpTime"), None);
+}
@@ -8,18 +8,76 @@ extern crate libc;
 pub use self::rfc3164Facility::Rfc3164Facility;
 mod rfc3164Facility;
 
-pub use self::syslog2Sender::SyslogSender;
-mod syslog2Sender;
+pub use self::syslogSender::SyslogSender;
+mod syslogSender;
+
+pub use self::syslogSenderMetrics::SyslogSenderMetrics;
+mod syslogSenderMetrics;
+
+pub use self::syslogSenderMetricsSnapshot::SendLatencyHistogramUpperBoundsInMicroseconds;
+pub use self::syslogSenderMetricsSnapshot::SyslogSenderMetricsSnapshot;
+mod syslogSenderMetricsSnapshot;
+
+pub use self::measuringSyslogSender::MeasuringSyslogSender;
+mod measuringSyslogSender;
 
 pub use self::insecureBlockingUdpSyslogSender::InsecureBlockingUdpSyslogSender;
 mod insecureBlockingUdpSyslogSender;
 
-pub use self::insecureThreadUnsafeBlockingTcpSyslogSender::InsecureThreadUnsafeBlockingTcpSyslogSender;
-mod insecureThreadUnsafeBlockingTcpSyslogSender;
+pub use self::insecureBlockingTcpSyslogSender::InsecureBlockingTcpSyslogSender;
+#[allow(deprecated)]
+pub use self::insecureBlockingTcpSyslogSender::InsecureThreadUnsafeBlockingTcpSyslogSender;
+mod insecureBlockingTcpSyslogSender;
 
 pub use self::posixSyslogSender::PosixSyslogSender;
 mod posixSyslogSender;
 
+pub use self::consoleSyslogSender::ConsoleSyslogSender;
+mod consoleSyslogSender;
+
+pub use self::sdDaemonSyslogSender::SdDaemonSyslogSender;
+mod sdDaemonSyslogSender;
+
+pub use self::capturedMessage::CapturedMessage;
+mod capturedMessage;
+
+pub use self::capturingSyslogSender::CapturingSyslogSender;
+mod capturingSyslogSender;
+
+pub use self::rateLimitingSyslogSender::RateLimitKey;
+pub use self::rateLimitingSyslogSender::RateLimitingSyslogSender;
+mod rateLimitingSyslogSender;
+
+pub use self::repeatedMessageIdentity::RepeatedMessageIdentity;
+pub use self::repeatedMessageIdentity::COMPARE_FACILITY;
+pub use self::repeatedMessageIdentity::COMPARE_SEVERITY;
+pub use self::repeatedMessageIdentity::COMPARE_MESSAGE_ID;
+pub use self::repeatedMessageIdentity::COMPARE_STRUCTURED_DATA;
+pub use self::repeatedMessageIdentity::COMPARE_MESSAGE;
+pub use self::repeatedMessageIdentity::COMPARE_APP_NAME;
+mod repeatedMessageIdentity;
+
+pub use self::repeatSuppressingSyslogSender::RepeatSuppressingSyslogSender;
+mod repeatSuppressingSyslogSender;
+
+pub use self::sampleRate::SampleRate;
+mod sampleRate;
+
+pub use self::samplingSyslogSender::SamplingSyslogSender;
+mod samplingSyslogSender;
+
+pub use self::maskingSyslogSender::MaskingSyslogSender;
+mod maskingSyslogSender;
+
+pub use self::selectingSyslogSender::SelectingSyslogSender;
+mod selectingSyslogSender;
+
+pub use self::timeQualitySyslogSender::TimeQualitySyslogSender;
+mod timeQualitySyslogSender;
+
+pub use self::metaSyslogSender::MetaSyslogSender;
+mod metaSyslogSender;
+
 // #[test]
 // fn format_message_rfc3164_test()
 // {
@@ -6,17 +6,22 @@ extern crate process;
 extern crate time;
 extern crate string_utilities;
 use std::io::Result;
-use syslog2Senders::Rfc3164Facility;
-use syslog2Senders::SyslogSender;
+use std::time::Instant;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetrics;
+use syslogSenders::SyslogSenderMetricsSnapshot;
 use Severity;
-use syslog2_cstr_withFacility;
+use syslog_cstr_withFacility;
 use self::string_utilities::to_cstr_best_effort;
 use rfc5424::StructuredData;
+use MessageHeaderOverrides;
 
 #[allow(dead_code)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug)]
 pub struct PosixSyslogSender
 {
+	metrics: SyslogSenderMetrics,
 }
 
 impl PosixSyslogSender
@@ -26,23 +31,46 @@ impl PosixSyslogSender
 	fn new() -> Result<PosixSyslogSender>
 	{
 		Ok(PosixSyslogSender
-		{	
+		{
+			metrics: SyslogSenderMetrics::new(),
 		})
 	}
 }
 
 impl SyslogSender for PosixSyslogSender
 {
+	#[inline(always)]
 	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
 	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	#[allow(unused_variables)]
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		let startedAt = Instant::now();
+		
 		let (cStringMessage, errorOption) = to_cstr_best_effort(message);
 		
-		syslog2_cstr_withFacility(severity, &cStringMessage, rfc3164Facility.toFacilityMappingSolarisToDaemon());
+		syslog_cstr_withFacility(severity, &cStringMessage, rfc3164Facility.toFacilityMappingSolarisToDaemon());
 		
-		match errorOption
+		// The message was still logged, but only as much of it as could be converted
+		let result = match errorOption
 		{
 			None => Ok(()),
-			Some(error) => Err(error),
-		}
+			Some(error) =>
+			{
+				self.metrics.recordTruncation();
+				Err(error)
+			},
+		};
+		
+		self.metrics.record(cStringMessage.as_bytes().len(), startedAt, &result);
+		result
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		Some(self.metrics.snapshot())
 	}
 }
@@ -0,0 +1,389 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::collections::HashMap;
+use std::io::Result;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use rfc5424::StructuredData;
+use Severity;
+use LogMask;
+use MessageHeaderOverrides;
+
+
+/// What messages share a token bucket
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum RateLimitKey
+{
+	/// One bucket for everything sent
+	Sender,
+	
+	/// One bucket per severity
+	Severity,
+	
+	/// One bucket per MSGID, as given in `MessageHeaderOverrides`; messages without an overridden MSGID share a bucket
+	MessageId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Bucket
+{
+	Sender,
+	Severity(Severity),
+	MessageId(Option<String>),
+}
+
+#[derive(Debug)]
+struct TokenBucket
+{
+	tokens: f64,
+	lastRefilledAt: Instant,
+	suppressed: usize,
+	
+	/// Of the most recently suppressed message, so that a summary can be sent by `flush()`
+	lastSuppressed: Option<(Rfc3164Facility, Severity)>,
+}
+
+impl TokenBucket
+{
+	#[inline(always)]
+	fn new(burst: f64, now: Instant) -> TokenBucket
+	{
+		TokenBucket
+		{
+			tokens: burst,
+			lastRefilledAt: now,
+			suppressed: 0,
+			lastSuppressed: None,
+		}
+	}
+	
+	fn refill(&mut self, now: Instant, burst: f64, tokensPerSecond: f64)
+	{
+		// Instants passed to a sender are not guaranteed to be in order across threads
+		if now <= self.lastRefilledAt
+		{
+			return;
+		}
+		
+		let elapsed = now.duration_since(self.lastRefilledAt);
+		let elapsedInSeconds = elapsed.as_secs() as f64 + (elapsed.subsec_nanos() as f64 / 1_000_000_000.0);
+		let refilled = self.tokens + elapsedInSeconds * tokensPerSecond;
+		self.tokens = if refilled > burst { burst } else { refilled };
+		self.lastRefilledAt = now;
+	}
+	
+	/// A full bucket with nothing suppressed behaves exactly as a new one, so can be discarded
+	#[inline(always)]
+	fn isIdle(&self, burst: f64) -> bool
+	{
+		self.suppressed == 0 && self.tokens >= burst
+	}
+}
+
+#[derive(Debug)]
+struct TokenBuckets
+{
+	buckets: HashMap<Bucket, TokenBucket>,
+	evictIdleWhenLengthReaches: usize,
+}
+
+const MinimumBucketsBeforeEviction: usize = 256;
+
+/// Wraps a `SyslogSender` with a token bucket per key; each bucket holds up to `burst` messages and refills at `burst` messages per `interval`
+/// Messages sent when a bucket is empty are dropped and counted
+/// The first message sent after a bucket has refilled is preceded by a summary of how many were dropped, in the manner of rsyslog's imuxsock; summaries still pending are sent by `flush()` and on drop
+/// Buckets which have refilled completely are discarded once there are many of them, so that keying by MSGID does not grow without bound
+#[derive(Debug)]
+pub struct RateLimitingSyslogSender<S: SyslogSender>
+{
+	sender: S,
+	key: RateLimitKey,
+	burst: f64,
+	tokensPerSecond: f64,
+	exemptions: LogMask,
+	buckets: Mutex<TokenBuckets>,
+	dropped: AtomicUsize,
+}
+
+impl <S: SyslogSender> RateLimitingSyslogSender<S>
+{
+	/// Messages of a severity in `exemptions` are never rate-limited nor do they consume tokens; typically `LOG_EMERG` and `LOG_ALERT`, eg `LogMask::upto(Severity::LOG_ALERT)`
+	/// Panics if `burst` or `interval` is zero
+	pub fn new(sender: S, key: RateLimitKey, burst: u32, interval: Duration, exemptions: LogMask) -> RateLimitingSyslogSender<S>
+	{
+		assert!(burst != 0, "burst can not be zero");
+		let intervalInSeconds = interval.as_secs() as f64 + (interval.subsec_nanos() as f64 / 1_000_000_000.0);
+		assert!(intervalInSeconds > 0.0, "interval can not be zero");
+		
+		RateLimitingSyslogSender
+		{
+			sender: sender,
+			key: key,
+			burst: burst as f64,
+			tokensPerSecond: burst as f64 / intervalInSeconds,
+			exemptions: exemptions,
+			buckets: Mutex::new(TokenBuckets
+			{
+				buckets: HashMap::new(),
+				evictIdleWhenLengthReaches: MinimumBucketsBeforeEviction,
+			}),
+			dropped: AtomicUsize::new(0),
+		}
+	}
+	
+	/// Total number of messages dropped since creation
+	pub fn dropped(&self) -> usize
+	{
+		self.dropped.load(Ordering::Relaxed)
+	}
+	
+	/// Sends a summary for every bucket which has suppressed messages since it last let one through
+	/// Returns the first error, but tries to send every summary
+	pub fn flush(&self) -> Result<()>
+	{
+		let mut pending = Vec::new();
+		{
+			let mut tokenBuckets = self.buckets.lock().unwrap();
+			for (bucket, tokenBucket) in tokenBuckets.buckets.iter_mut()
+			{
+				if let Some(lastSuppressed) = tokenBucket.lastSuppressed.take()
+				{
+					pending.push((bucket.clone(), lastSuppressed, tokenBucket.suppressed));
+					tokenBucket.suppressed = 0;
+				}
+			}
+		}
+		
+		let mut result = Ok(());
+		for (bucket, (rfc3164Facility, severity), suppressed) in pending
+		{
+			let messageHeaderOverrides = match bucket
+			{
+				Bucket::MessageId(Some(ref messageId)) => MessageHeaderOverrides::messageId(messageId),
+				_ => MessageHeaderOverrides::default(),
+			};
+			let summary = RateLimitingSyslogSender::<S>::summary(&bucket, suppressed);
+			let sent = self.sender.sendWithOverrides(rfc3164Facility, severity, &messageHeaderOverrides, &StructuredData::new(), &summary);
+			if result.is_ok()
+			{
+				result = sent;
+			}
+		}
+		result
+	}
+	
+	#[inline(always)]
+	fn isExempt(&self, severity: Severity) -> bool
+	{
+		self.exemptions.contains(severity)
+	}
+	
+	fn bucket(&self, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides) -> Bucket
+	{
+		match self.key
+		{
+			RateLimitKey::Sender => Bucket::Sender,
+			RateLimitKey::Severity => Bucket::Severity(severity),
+			RateLimitKey::MessageId => Bucket::MessageId(messageHeaderOverrides.messageId.map(|messageId| messageId.to_owned())),
+		}
+	}
+	
+	/// Returns None if the message should be dropped, otherwise the number of messages suppressed since the last one that was not
+	fn acquireToken(&self, bucket: Bucket, rfc3164Facility: Rfc3164Facility, severity: Severity, now: Instant) -> Option<usize>
+	{
+		let mut tokenBuckets = self.buckets.lock().unwrap();
+		
+		if !tokenBuckets.buckets.contains_key(&bucket) && tokenBuckets.buckets.len() >= tokenBuckets.evictIdleWhenLengthReaches
+		{
+			self.evictIdle(&mut tokenBuckets, now);
+		}
+		
+		let burst = self.burst;
+		let tokenBucket = tokenBuckets.buckets.entry(bucket).or_insert_with(|| TokenBucket::new(burst, now));
+		tokenBucket.refill(now, self.burst, self.tokensPerSecond);
+		
+		if tokenBucket.tokens < 1.0
+		{
+			tokenBucket.suppressed += 1;
+			tokenBucket.lastSuppressed = Some((rfc3164Facility, severity));
+			self.dropped.fetch_add(1, Ordering::Relaxed);
+			return None;
+		}
+		
+		tokenBucket.tokens -= 1.0;
+		let suppressed = tokenBucket.suppressed;
+		tokenBucket.suppressed = 0;
+		tokenBucket.lastSuppressed = None;
+		Some(suppressed)
+	}
+	
+	/// The next eviction is when the number of buckets has doubled, so that the cost of eviction is amortised
+	fn evictIdle(&self, tokenBuckets: &mut TokenBuckets, now: Instant)
+	{
+		let burst = self.burst;
+		let tokensPerSecond = self.tokensPerSecond;
+		tokenBuckets.buckets.retain(|_, tokenBucket|
+		{
+			tokenBucket.refill(now, burst, tokensPerSecond);
+			!tokenBucket.isIdle(burst)
+		});
+		
+		let length = tokenBuckets.buckets.len();
+		tokenBuckets.evictIdleWhenLengthReaches = if length * 2 > MinimumBucketsBeforeEviction
+		{
+			length * 2
+		}
+		else
+		{
+			MinimumBucketsBeforeEviction
+		};
+	}
+	
+	fn summary(bucket: &Bucket, suppressed: usize) -> String
+	{
+		let messages = match suppressed
+		{
+			1 => "message",
+			_ => "messages",
+		};
+		
+		match *bucket
+		{
+			Bucket::Sender | Bucket::MessageId(None) => format!("{} {} suppressed due to rate-limiting", suppressed, messages),
+			Bucket::Severity(severity) => format!("{} {} of severity {} suppressed due to rate-limiting", suppressed, messages, severity),
+			Bucket::MessageId(Some(ref messageId)) => format!("{} {} with MSGID {} suppressed due to rate-limiting", suppressed, messages, messageId),
+		}
+	}
+}
+
+impl <S: SyslogSender> SyslogSender for RateLimitingSyslogSender<S>
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		if self.isExempt(severity)
+		{
+			return self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message);
+		}
+		
+		let bucket = self.bucket(severity, messageHeaderOverrides);
+		match self.acquireToken(bucket.clone(), rfc3164Facility, severity, Instant::now())
+		{
+			None => Ok(()),
+			Some(0) => self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message),
+			Some(suppressed) =>
+			{
+				// The message is sent even if the summary could not be
+				let summary = RateLimitingSyslogSender::<S>::summary(&bucket, suppressed);
+				let summarySent = self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, &StructuredData::new(), &summary);
+				try!(self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message));
+				summarySent
+			}
+		}
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		Some(self.sender.metrics().unwrap_or_default().withAdditionalDrops(self.dropped()))
+	}
+}
+
+impl <S: SyslogSender> Drop for RateLimitingSyslogSender<S>
+{
+	fn drop(&mut self)
+	{
+		// There is nowhere to report a failure
+		let _ = self.flush();
+	}
+}
+
+#[test]
+fn refillsTokensOverTime()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let rateLimitingSyslogSender = RateLimitingSyslogSender::new(CapturingSyslogSender::forTesting(), RateLimitKey::Sender, 2, Duration::from_secs(1), LogMask::empty());
+	let acquireToken = |now| rateLimitingSyslogSender.acquireToken(Bucket::Sender, Rfc3164Facility::user, Severity::LOG_INFO, now);
+	
+	let startedAt = Instant::now();
+	assert_eq!(acquireToken(startedAt), Some(0));
+	assert_eq!(acquireToken(startedAt), Some(0));
+	assert_eq!(acquireToken(startedAt), None);
+	assert_eq!(acquireToken(startedAt), None);
+	
+	// Two tokens per second, so one has been refilled
+	assert_eq!(acquireToken(startedAt + Duration::from_millis(500)), Some(2));
+	assert_eq!(acquireToken(startedAt + Duration::from_millis(500)), None);
+	
+	// Refilled to no more than burst
+	let muchLater = startedAt + Duration::from_secs(60);
+	assert_eq!(acquireToken(muchLater), Some(1));
+	assert_eq!(acquireToken(muchLater), Some(0));
+	assert_eq!(acquireToken(muchLater), None);
+	
+	assert_eq!(rateLimitingSyslogSender.dropped(), 4);
+}
+
+#[test]
+fn summarisesSuppressedMessagesAndExemptsSeverities()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let rateLimitingSyslogSender = RateLimitingSyslogSender::new(CapturingSyslogSender::forTesting(), RateLimitKey::MessageId, 1, Duration::from_secs(3600), LogMask::upto(Severity::LOG_ALERT));
+	let noStructuredData = StructuredData::new();
+	let messageIdA = MessageHeaderOverrides::messageId("A");
+	let messageIdB = MessageHeaderOverrides::messageId("B");
+	
+	rateLimitingSyslogSender.sendWithOverrides(Rfc3164Facility::user, Severity::LOG_INFO, &messageIdA, &noStructuredData, "first A").unwrap();
+	rateLimitingSyslogSender.sendWithOverrides(Rfc3164Facility::user, Severity::LOG_INFO, &messageIdA, &noStructuredData, "second A").unwrap();
+	rateLimitingSyslogSender.sendWithOverrides(Rfc3164Facility::user, Severity::LOG_ERR, &messageIdA, &noStructuredData, "third A").unwrap();
+	rateLimitingSyslogSender.sendWithOverrides(Rfc3164Facility::user, Severity::LOG_INFO, &messageIdB, &noStructuredData, "first B").unwrap();
+	for _ in 0 .. 3
+	{
+		rateLimitingSyslogSender.sendWithOverrides(Rfc3164Facility::user, Severity::LOG_EMERG, &messageIdA, &noStructuredData, "exempt").unwrap();
+	}
+	assert_eq!(rateLimitingSyslogSender.dropped(), 2);
+	
+	let sent: Vec<String> = rateLimitingSyslogSender.sender.messages().into_iter().map(|capturedMessage| capturedMessage.message).collect();
+	assert_eq!(sent, vec!["first A", "first B", "exempt", "exempt", "exempt"]);
+	
+	rateLimitingSyslogSender.flush().unwrap();
+	let summary = rateLimitingSyslogSender.sender.messages().pop().unwrap();
+	assert_eq!(summary.message, "2 messages with MSGID A suppressed due to rate-limiting");
+	assert_eq!(summary.messageId, Some("A".to_owned()));
+	assert_eq!(summary.severity, Severity::LOG_ERR);
+	
+	// Nothing is pending after a flush
+	rateLimitingSyslogSender.flush().unwrap();
+	assert_eq!(rateLimitingSyslogSender.sender.len(), 6);
+	
+	assert_eq!(RateLimitingSyslogSender::<CapturingSyslogSender>::summary(&Bucket::Severity(Severity::LOG_DEBUG), 3), "3 messages of severity debug suppressed due to rate-limiting");
+	assert_eq!(RateLimitingSyslogSender::<CapturingSyslogSender>::summary(&Bucket::Sender, 1), "1 message suppressed due to rate-limiting");
+}
+
+#[test]
+fn evictsIdleBuckets()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let rateLimitingSyslogSender = RateLimitingSyslogSender::new(CapturingSyslogSender::forTesting(), RateLimitKey::MessageId, 1, Duration::from_millis(1), LogMask::empty());
+	let startedAt = Instant::now();
+	for index in 0 .. MinimumBucketsBeforeEviction * 4
+	{
+		let bucket = Bucket::MessageId(Some(index.to_string()));
+		rateLimitingSyslogSender.acquireToken(bucket, Rfc3164Facility::user, Severity::LOG_INFO, startedAt + Duration::from_secs(index as u64));
+	}
+	assert!(rateLimitingSyslogSender.buckets.lock().unwrap().buckets.len() <= MinimumBucketsBeforeEviction);
+}
@@ -0,0 +1,301 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::io::Result;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+use syslogSenders::RepeatedMessageIdentity;
+use syslogSenders::COMPARE_FACILITY;
+use syslogSenders::COMPARE_SEVERITY;
+use syslogSenders::COMPARE_APP_NAME;
+use syslogSenders::COMPARE_MESSAGE_ID;
+use syslogSenders::COMPARE_STRUCTURED_DATA;
+use syslogSenders::COMPARE_MESSAGE;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use rfc5424::StructuredData;
+use Severity;
+use MessageHeaderOverrides;
+
+
+#[derive(Debug)]
+struct LastMessage
+{
+	rfc3164Facility: Rfc3164Facility,
+	severity: Severity,
+	appName: Option<String>,
+	messageId: Option<String>,
+	structuredData: Vec<Vec<u8>>,
+	message: String,
+	sentAt: Instant,
+	repeated: usize,
+}
+
+impl LastMessage
+{
+	fn isRepeatedBy(&self, identity: RepeatedMessageIdentity, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structuredData: &Vec<Vec<u8>>, message: &str) -> bool
+	{
+		if identity.contains(COMPARE_FACILITY) && self.rfc3164Facility != rfc3164Facility
+		{
+			return false;
+		}
+		
+		if identity.contains(COMPARE_SEVERITY) && self.severity != severity
+		{
+			return false;
+		}
+		
+		if identity.contains(COMPARE_APP_NAME) && self.appName.as_ref().map(|appName| &appName[..]) != messageHeaderOverrides.appName
+		{
+			return false;
+		}
+		
+		if identity.contains(COMPARE_MESSAGE_ID) && self.messageId.as_ref().map(|messageId| &messageId[..]) != messageHeaderOverrides.messageId
+		{
+			return false;
+		}
+		
+		if identity.contains(COMPARE_STRUCTURED_DATA) && &self.structuredData != structuredData
+		{
+			return false;
+		}
+		
+		if identity.contains(COMPARE_MESSAGE) && self.message != message
+		{
+			return false;
+		}
+		
+		true
+	}
+}
+
+/// Wraps a `SyslogSender` so that consecutive repeats of a message within `window` are collapsed into a single `last message repeated N times` record, as classic syslogd did
+/// The record is sent when a different message arrives, when a repeat arrives after `window` has elapsed since the repeated message was last sent, on `flush()` or on drop
+/// There is no timer, so if the repeats stop, the record is not sent until then; call `flush()` periodically if that is too late
+#[derive(Debug)]
+pub struct RepeatSuppressingSyslogSender<S: SyslogSender>
+{
+	sender: S,
+	window: Duration,
+	identity: RepeatedMessageIdentity,
+	lastMessage: Mutex<Option<LastMessage>>,
+	suppressed: AtomicUsize,
+}
+
+impl <S: SyslogSender> RepeatSuppressingSyslogSender<S>
+{
+	pub fn new(sender: S, window: Duration, identity: RepeatedMessageIdentity) -> RepeatSuppressingSyslogSender<S>
+	{
+		RepeatSuppressingSyslogSender
+		{
+			sender: sender,
+			window: window,
+			identity: identity,
+			lastMessage: Mutex::new(None),
+			suppressed: AtomicUsize::new(0),
+		}
+	}
+	
+	/// Total number of repeated messages collapsed since creation
+	pub fn suppressed(&self) -> usize
+	{
+		self.suppressed.load(Ordering::Relaxed)
+	}
+	
+	/// Sends a `last message repeated N times` record if any repeats have been suppressed
+	pub fn flush(&self) -> Result<()>
+	{
+		let mut lastMessage = self.lastMessage.lock().unwrap();
+		match *lastMessage
+		{
+			None => Ok(()),
+			Some(ref mut lastMessage) => self.sendRepeated(lastMessage),
+		}
+	}
+	
+	fn sendRepeated(&self, lastMessage: &mut LastMessage) -> Result<()>
+	{
+		if lastMessage.repeated == 0
+		{
+			return Ok(());
+		}
+		
+		let messageHeaderOverrides = MessageHeaderOverrides
+		{
+			appName: lastMessage.appName.as_ref().map(|appName| &appName[..]),
+			messageId: lastMessage.messageId.as_ref().map(|messageId| &messageId[..]),
+		};
+		let summary = format!("last message repeated {} times", lastMessage.repeated);
+		try!(self.sender.sendWithOverrides(lastMessage.rfc3164Facility, lastMessage.severity, &messageHeaderOverrides, &StructuredData::new(), &summary));
+		
+		// Only reset once sent, so that a later flush() can try again
+		lastMessage.repeated = 0;
+		Ok(())
+	}
+	
+	/// Elements are rendered in insertion order, so `[a][b]` and `[b][a]` are different messages, as they are when sent
+	fn renderStructuredData(structured_data_elements: &StructuredData) -> Vec<Vec<u8>>
+	{
+		structured_data_elements.iter().map(|structured_data_element|
+		{
+			let mut renderedElement = Vec::with_capacity(64);
+			structured_data_element.write(&mut renderedElement);
+			renderedElement
+		}).collect()
+	}
+}
+
+impl <S: SyslogSender> SyslogSender for RepeatSuppressingSyslogSender<S>
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		let now = Instant::now();
+		
+		let structuredData = if self.identity.contains(COMPARE_STRUCTURED_DATA)
+		{
+			RepeatSuppressingSyslogSender::<S>::renderStructuredData(structured_data_elements)
+		}
+		else
+		{
+			Vec::new()
+		};
+		
+		// The lock is held whilst sending so that a repeated record can not be overtaken by a later message
+		let mut lastMessage = self.lastMessage.lock().unwrap();
+		
+		// The message is sent even if the repeated record could not be; the record's error is then returned
+		let mut repeatedSent = Ok(());
+		if let Some(ref mut lastMessage) = *lastMessage
+		{
+			if lastMessage.isRepeatedBy(self.identity, rfc3164Facility, severity, messageHeaderOverrides, &structuredData, message)
+			{
+				if now.duration_since(lastMessage.sentAt) < self.window
+				{
+					lastMessage.repeated += 1;
+					self.suppressed.fetch_add(1, Ordering::Relaxed);
+					return Ok(());
+				}
+				
+				let repeatedSent = self.sendRepeated(lastMessage);
+				lastMessage.sentAt = now;
+				try!(self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message));
+				return repeatedSent;
+			}
+			
+			repeatedSent = self.sendRepeated(lastMessage);
+		}
+		
+		*lastMessage = Some(LastMessage
+		{
+			rfc3164Facility: rfc3164Facility,
+			severity: severity,
+			appName: messageHeaderOverrides.appName.map(|appName| appName.to_owned()),
+			messageId: messageHeaderOverrides.messageId.map(|messageId| messageId.to_owned()),
+			structuredData: structuredData,
+			message: message.to_owned(),
+			sentAt: now,
+			repeated: 0,
+		});
+		
+		try!(self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message));
+		repeatedSent
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		Some(self.sender.metrics().unwrap_or_default().withAdditionalDrops(self.suppressed()))
+	}
+}
+
+impl <S: SyslogSender> Drop for RepeatSuppressingSyslogSender<S>
+{
+	fn drop(&mut self)
+	{
+		// There is nowhere to report a failure
+		let _ = self.flush();
+	}
+}
+
+#[test]
+fn collapsesRepeatsWithinWindow()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let repeatSuppressingSyslogSender = RepeatSuppressingSyslogSender::new(CapturingSyslogSender::forTesting(), Duration::from_secs(3600), RepeatedMessageIdentity::default());
+	let sent = || repeatSuppressingSyslogSender.sender.messages().into_iter().map(|capturedMessage| capturedMessage.message).collect::<Vec<String>>();
+	let noStructuredData = StructuredData::new();
+	
+	for _ in 0 .. 3
+	{
+		repeatSuppressingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_INFO, &noStructuredData, "A").unwrap();
+	}
+	assert_eq!(sent(), vec!["A"]);
+	assert_eq!(repeatSuppressingSyslogSender.suppressed(), 2);
+	
+	// A different severity is not a repeat
+	repeatSuppressingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_ERR, &noStructuredData, "A").unwrap();
+	assert_eq!(sent(), vec!["A", "last message repeated 2 times", "A"]);
+	
+	repeatSuppressingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_ERR, &noStructuredData, "A").unwrap();
+	repeatSuppressingSyslogSender.flush().unwrap();
+	repeatSuppressingSyslogSender.flush().unwrap();
+	assert_eq!(sent(), vec!["A", "last message repeated 2 times", "A", "last message repeated 1 times"]);
+	assert_eq!(repeatSuppressingSyslogSender.sender.messages()[3].severity, Severity::LOG_ERR);
+}
+
+#[test]
+fn sendsRepeatsAfterWindow()
+{
+	use std::thread::sleep;
+	use syslogSenders::CapturingSyslogSender;
+	
+	let repeatSuppressingSyslogSender = RepeatSuppressingSyslogSender::new(CapturingSyslogSender::forTesting(), Duration::from_millis(200), RepeatedMessageIdentity::default());
+	let noStructuredData = StructuredData::new();
+	
+	repeatSuppressingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_INFO, &noStructuredData, "A").unwrap();
+	repeatSuppressingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_INFO, &noStructuredData, "A").unwrap();
+	sleep(Duration::from_millis(300));
+	repeatSuppressingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_INFO, &noStructuredData, "A").unwrap();
+	
+	let sent: Vec<String> = repeatSuppressingSyslogSender.sender.messages().into_iter().map(|capturedMessage| capturedMessage.message).collect();
+	assert_eq!(sent, vec!["A", "last message repeated 1 times", "A"]);
+}
+
+#[test]
+fn structuredDataInADifferentOrderIsNotARepeat()
+{
+	use std::borrow::Cow;
+	use rfc5424::StructuredDataElement;
+	use rfc5424::TruncatedUsAsciiPrintableString;
+	use syslogSenders::CapturingSyslogSender;
+	
+	let first = TruncatedUsAsciiPrintableString::new_sd_name("a@38188");
+	let second = TruncatedUsAsciiPrintableString::new_sd_name("b@38188");
+	let name = TruncatedUsAsciiPrintableString::new_sd_name("name");
+	let element = |id| StructuredDataElement::new(id, vec![name.parameter(Cow::Borrowed("value"))]);
+	
+	let mut inOrder = StructuredData::new();
+	inOrder.insert(element(&first)).unwrap();
+	inOrder.insert(element(&second)).unwrap();
+	let mut reversed = StructuredData::new();
+	reversed.insert(element(&second)).unwrap();
+	reversed.insert(element(&first)).unwrap();
+	
+	let repeatSuppressingSyslogSender = RepeatSuppressingSyslogSender::new(CapturingSyslogSender::forTesting(), Duration::from_secs(3600), RepeatedMessageIdentity::default());
+	repeatSuppressingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_INFO, &inOrder, "A").unwrap();
+	repeatSuppressingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_INFO, &reversed, "A").unwrap();
+	repeatSuppressingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_INFO, &reversed, "A").unwrap();
+	
+	assert_eq!(repeatSuppressingSyslogSender.sender.len(), 2);
+	assert_eq!(repeatSuppressingSyslogSender.suppressed(), 1);
+}
@@ -0,0 +1,27 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+
+bitflags!
+{
+	/// Which parts of a message must be equal for it to be considered a repeat of the previous one
+	pub flags RepeatedMessageIdentity: u8
+	{
+		const COMPARE_FACILITY = 0x01,
+		const COMPARE_SEVERITY = 0x02,
+		const COMPARE_MESSAGE_ID = 0x04,
+		const COMPARE_STRUCTURED_DATA = 0x08,
+		const COMPARE_MESSAGE = 0x10,
+		const COMPARE_APP_NAME = 0x20,
+	}
+}
+
+impl Default for RepeatedMessageIdentity
+{
+	/// Defaults to comparing everything, as classic syslogd did
+	#[inline(always)]
+	fn default() -> RepeatedMessageIdentity
+	{
+		RepeatedMessageIdentity::all()
+	}
+}
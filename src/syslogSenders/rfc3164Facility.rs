@@ -9,7 +9,7 @@ use Facility;
 /// Use of numbers 12 - 15 inclusive or above 23 is not cross-platform compatible and any message recieved with these codes should, regardless of RFC 3164, be considered private use
 /// and recorded or used in a manner compatible with 'secret' (some of these codes on BSD systems are used for LOG_SECURITY and LOG_CONSOLE).
 #[allow(non_camel_case_types)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(i32)]
 pub enum Rfc3164Facility
 {
@@ -85,6 +85,63 @@ impl Rfc3164Facility
 		}
 	}
 	
+	/// The name used in selectors, eg `local0`; `clock` is named `cron`, as it is by sysklogd and rsyslog
+	pub fn name(self) -> &'static str
+	{
+		match self
+		{
+			Rfc3164Facility::kern => "kern",
+			Rfc3164Facility::user => "user",
+			Rfc3164Facility::mail => "mail",
+			Rfc3164Facility::daemon => "daemon",
+			Rfc3164Facility::auth => "auth",
+			Rfc3164Facility::syslog2 => "syslog",
+			Rfc3164Facility::lpr => "lpr",
+			Rfc3164Facility::news => "news",
+			Rfc3164Facility::uucp => "uucp",
+			Rfc3164Facility::clock => "cron",
+			Rfc3164Facility::authpriv => "authpriv",
+			Rfc3164Facility::ftp => "ftp",
+			Rfc3164Facility::local0 => "local0",
+			Rfc3164Facility::local1 => "local1",
+			Rfc3164Facility::local2 => "local2",
+			Rfc3164Facility::local3 => "local3",
+			Rfc3164Facility::local4 => "local4",
+			Rfc3164Facility::local5 => "local5",
+			Rfc3164Facility::local6 => "local6",
+			Rfc3164Facility::local7 => "local7",
+		}
+	}
+	
+	/// Also accepts the aliases `clock` and `security` (for `auth`)
+	pub fn fromName(name: &str) -> Option<Rfc3164Facility>
+	{
+		match name
+		{
+			"kern" => Some(Rfc3164Facility::kern),
+			"user" => Some(Rfc3164Facility::user),
+			"mail" => Some(Rfc3164Facility::mail),
+			"daemon" => Some(Rfc3164Facility::daemon),
+			"auth" | "security" => Some(Rfc3164Facility::auth),
+			"syslog" => Some(Rfc3164Facility::syslog2),
+			"lpr" => Some(Rfc3164Facility::lpr),
+			"news" => Some(Rfc3164Facility::news),
+			"uucp" => Some(Rfc3164Facility::uucp),
+			"cron" | "clock" => Some(Rfc3164Facility::clock),
+			"authpriv" => Some(Rfc3164Facility::authpriv),
+			"ftp" => Some(Rfc3164Facility::ftp),
+			"local0" => Some(Rfc3164Facility::local0),
+			"local1" => Some(Rfc3164Facility::local1),
+			"local2" => Some(Rfc3164Facility::local2),
+			"local3" => Some(Rfc3164Facility::local3),
+			"local4" => Some(Rfc3164Facility::local4),
+			"local5" => Some(Rfc3164Facility::local5),
+			"local6" => Some(Rfc3164Facility::local6),
+			"local7" => Some(Rfc3164Facility::local7),
+			_ => None,
+		}
+	}
+	
 	pub fn toFacilityMappingSolarisToDaemon(self) -> Facility
 	{
 		self.toFacilityAccommodatingSolaris().unwrap_or(Facility::LOG_DAEMON)
@@ -0,0 +1,65 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate rand;
+use self::rand::Rng;
+
+
+/// How many messages are kept
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SampleRate
+{
+	/// Keep everything; kept messages are not annotated
+	All,
+	
+	/// Keep exactly every Nth message; zero is treated as one
+	OneIn(u32),
+	
+	/// Keep each message with this probability, expressed as a percentage between 0 and 100
+	Percentage(f64),
+}
+
+impl SampleRate
+{
+	/// `counter` is the number of messages previously considered by this rate; `random` is only used for `Percentage`
+	pub fn keep<R: Rng>(&self, counter: usize, random: &mut R) -> bool
+	{
+		match *self
+		{
+			SampleRate::All => true,
+			SampleRate::OneIn(0) => true,
+			SampleRate::OneIn(n) => counter % (n as usize) == 0,
+			SampleRate::Percentage(percentage) => random.gen::<f64>() * 100.0 < percentage,
+		}
+	}
+	
+	/// The number of messages each kept message stands for, or None if all are kept
+	/// Fractional weights are given to no more than 2 decimal places, eg `3.33` for 30%
+	pub fn weight(&self) -> Option<String>
+	{
+		match *self
+		{
+			SampleRate::All => None,
+			SampleRate::OneIn(0) | SampleRate::OneIn(1) => None,
+			SampleRate::OneIn(n) => Some(n.to_string()),
+			SampleRate::Percentage(percentage) if percentage >= 100.0 => None,
+			SampleRate::Percentage(percentage) =>
+			{
+				let weight = format!("{:.2}", 100.0 / percentage);
+				Some(weight.trim_end_matches('0').trim_end_matches('.').to_owned())
+			}
+		}
+	}
+}
+
+#[test]
+fn weightsAreGivenToFixedPrecision()
+{
+	assert_eq!(SampleRate::All.weight(), None);
+	assert_eq!(SampleRate::OneIn(1).weight(), None);
+	assert_eq!(SampleRate::OneIn(10).weight(), Some("10".to_owned()));
+	assert_eq!(SampleRate::Percentage(100.0).weight(), None);
+	assert_eq!(SampleRate::Percentage(30.0).weight(), Some("3.33".to_owned()));
+	assert_eq!(SampleRate::Percentage(40.0).weight(), Some("2.5".to_owned()));
+	assert_eq!(SampleRate::Percentage(50.0).weight(), Some("2".to_owned()));
+}
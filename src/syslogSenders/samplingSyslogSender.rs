@@ -0,0 +1,237 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate rand;
+use self::rand::SeedableRng;
+use self::rand::XorShiftRng;
+use std::collections::HashMap;
+use std::io::Result;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SampleRate;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use rfc5424::StructuredData;
+use rfc5424::StructuredDataElement;
+use Severity;
+use MessageHeaderOverrides;
+
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum SampleRule
+{
+	Default,
+	MessageId(String),
+	StructuredDataParameter(usize),
+}
+
+#[derive(Debug)]
+struct StructuredDataParameterSampleRate
+{
+	sdId: String,
+	name: String,
+	value: String,
+	sampleRate: SampleRate,
+}
+
+/// Wraps a `SyslogSender` so that only a sample of messages at or below a severity (eg `LOG_DEBUG`) are kept
+/// Rates are chosen by, in order, the first matching structured data parameter, the MSGID (as given in `MessageHeaderOverrides`) and then the default
+/// Kept messages are annotated with a `sampling@38188` element recording the rate, so that downstream tools can re-weight counts
+#[derive(Debug)]
+pub struct SamplingSyslogSender<S: SyslogSender>
+{
+	sender: S,
+	mostSevereSampled: Severity,
+	defaultSampleRate: SampleRate,
+	perMessageId: HashMap<String, SampleRate>,
+	perStructuredDataParameter: Vec<StructuredDataParameterSampleRate>,
+	counters: Mutex<HashMap<SampleRule, usize>>,
+	random: Mutex<XorShiftRng>,
+	dropped: AtomicUsize,
+}
+
+impl <S: SyslogSender> SamplingSyslogSender<S>
+{
+	/// Messages of severity `atOrBelow` or less severe (eg `LOG_INFO` includes `LOG_DEBUG`) are sampled at `defaultSampleRate`
+	#[inline(always)]
+	pub fn new(sender: S, atOrBelow: Severity, defaultSampleRate: SampleRate) -> SamplingSyslogSender<S>
+	{
+		SamplingSyslogSender::newWithRandom(sender, atOrBelow, defaultSampleRate, rand::weak_rng())
+	}
+	
+	/// As `new()`, but `SampleRate::Percentage` decisions are reproducible for a given `seed`; `seed` can not be all zeroes
+	#[inline(always)]
+	pub fn newWithSeed(sender: S, atOrBelow: Severity, defaultSampleRate: SampleRate, seed: [u32; 4]) -> SamplingSyslogSender<S>
+	{
+		SamplingSyslogSender::newWithRandom(sender, atOrBelow, defaultSampleRate, XorShiftRng::from_seed(seed))
+	}
+	
+	fn newWithRandom(sender: S, atOrBelow: Severity, defaultSampleRate: SampleRate, random: XorShiftRng) -> SamplingSyslogSender<S>
+	{
+		SamplingSyslogSender
+		{
+			sender: sender,
+			mostSevereSampled: atOrBelow,
+			defaultSampleRate: defaultSampleRate,
+			perMessageId: HashMap::new(),
+			perStructuredDataParameter: Vec::new(),
+			counters: Mutex::new(HashMap::new()),
+			random: Mutex::new(random),
+			dropped: AtomicUsize::new(0),
+		}
+	}
+	
+	/// Total number of messages not kept since creation
+	pub fn dropped(&self) -> usize
+	{
+		self.dropped.load(Ordering::Relaxed)
+	}
+	
+	pub fn sampleMessageId(&mut self, messageId: &str, sampleRate: SampleRate)
+	{
+		self.perMessageId.insert(messageId.to_owned(), sampleRate);
+	}
+	
+	/// Applies to messages with a structured data element `sdId` having a parameter `name` equal to `value`; rules are tried in the order added
+	pub fn sampleStructuredDataParameter(&mut self, sdId: &str, name: &str, value: &str, sampleRate: SampleRate)
+	{
+		self.perStructuredDataParameter.push(StructuredDataParameterSampleRate
+		{
+			sdId: sdId.to_owned(),
+			name: name.to_owned(),
+			value: value.to_owned(),
+			sampleRate: sampleRate,
+		});
+	}
+	
+	#[inline(always)]
+	fn isSampled(&self, severity: Severity) -> bool
+	{
+		self.mostSevereSampled.is_at_least_as_severe_as(severity)
+	}
+	
+	fn sampleRule(&self, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData) -> (SampleRule, SampleRate)
+	{
+		for (index, rule) in self.perStructuredDataParameter.iter().enumerate()
+		{
+			for structured_data_element in structured_data_elements.iter().filter(|structured_data_element| structured_data_element.id().as_str() == rule.sdId)
+			{
+				if structured_data_element.parameters().iter().any(|parameter| parameter.name().as_str() == rule.name && parameter.value() == rule.value)
+				{
+					return (SampleRule::StructuredDataParameter(index), rule.sampleRate);
+				}
+			}
+		}
+		
+		if let Some(messageId) = messageHeaderOverrides.messageId
+		{
+			if let Some(sampleRate) = self.perMessageId.get(messageId)
+			{
+				return (SampleRule::MessageId(messageId.to_owned()), *sampleRate);
+			}
+		}
+		
+		(SampleRule::Default, self.defaultSampleRate)
+	}
+	
+	fn keep(&self, sampleRule: SampleRule, sampleRate: SampleRate) -> bool
+	{
+		let mut counters = self.counters.lock().unwrap();
+		let counter = counters.entry(sampleRule).or_insert(0);
+		let keep = sampleRate.keep(*counter, &mut *self.random.lock().unwrap());
+		*counter = counter.wrapping_add(1);
+		keep
+	}
+}
+
+impl <S: SyslogSender> SyslogSender for SamplingSyslogSender<S>
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		if !self.isSampled(severity)
+		{
+			return self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message);
+		}
+		
+		let (sampleRule, sampleRate) = self.sampleRule(messageHeaderOverrides, structured_data_elements);
+		if !self.keep(sampleRule, sampleRate)
+		{
+			self.dropped.fetch_add(1, Ordering::Relaxed);
+			return Ok(());
+		}
+		
+		match sampleRate.weight()
+		{
+			None => self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message),
+			
+			// Already sampled, eg by an outer SamplingSyslogSender, whose weight is kept
+			Some(_) if structured_data_elements.contains("sampling@38188") => self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message),
+			
+			Some(weight) =>
+			{
+				let mut annotated = structured_data_elements.clone();
+				try!(annotated.insert(StructuredDataElement::sampling(weight)));
+				self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, &annotated, message)
+			}
+		}
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		Some(self.sender.metrics().unwrap_or_default().withAdditionalDrops(self.dropped()))
+	}
+}
+
+#[test]
+fn samplesOnlyLessSevereMessages()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let mut samplingSyslogSender = SamplingSyslogSender::newWithSeed(CapturingSyslogSender::forTesting(), Severity::LOG_INFO, SampleRate::OneIn(3), [1, 2, 3, 4]);
+	samplingSyslogSender.sampleMessageId("noisy", SampleRate::OneIn(5));
+	let noStructuredData = StructuredData::new();
+	
+	for index in 0 .. 6
+	{
+		samplingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_DEBUG, &noStructuredData, &format!("debug {}", index)).unwrap();
+		samplingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_NOTICE, &noStructuredData, &format!("notice {}", index)).unwrap();
+		samplingSyslogSender.sendWithOverrides(Rfc3164Facility::user, Severity::LOG_INFO, &MessageHeaderOverrides::messageId("noisy"), &noStructuredData, &format!("noisy {}", index)).unwrap();
+	}
+	
+	let capturedMessages = samplingSyslogSender.sender.messages();
+	let sent: Vec<&str> = capturedMessages.iter().map(|capturedMessage| &capturedMessage.message[..]).collect();
+	assert_eq!(sent, vec!["debug 0", "notice 0", "noisy 0", "notice 1", "notice 2", "debug 3", "notice 3", "notice 4", "notice 5", "noisy 5"]);
+	assert_eq!(samplingSyslogSender.dropped(), 8);
+	
+	assert_eq!(capturedMessages[0].structuredDataParameter("sampling@38188", "rate"), Some("3"));
+	assert_eq!(capturedMessages[1].structuredDataParameter("sampling@38188", "rate"), None);
+	assert_eq!(capturedMessages[2].structuredDataParameter("sampling@38188", "rate"), Some("5"));
+}
+
+#[test]
+fn percentagesAreReproducibleForASeed()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let sample = ||
+	{
+		let samplingSyslogSender = SamplingSyslogSender::newWithSeed(CapturingSyslogSender::forTesting(), Severity::LOG_DEBUG, SampleRate::Percentage(25.0), [5, 6, 7, 8]);
+		for index in 0 .. 1000
+		{
+			samplingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_DEBUG, &StructuredData::new(), &index.to_string()).unwrap();
+		}
+		samplingSyslogSender.sender.messages().into_iter().map(|capturedMessage| capturedMessage.message).collect::<Vec<String>>()
+	};
+	
+	let first = sample();
+	assert_eq!(first, sample());
+	assert!(first.len() > 150 && first.len() < 350, "kept {} of 1000 at 25%", first.len());
+}
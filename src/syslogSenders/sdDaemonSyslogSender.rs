@@ -0,0 +1,168 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate libc;
+use std::env::var_os;
+use std::io::Result;
+use std::io::Write;
+use std::io::stderr;
+use std::time::Instant;
+use std::mem::zeroed;
+use self::libc::fstat;
+use self::libc::stat;
+use self::libc::STDERR_FILENO;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetrics;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use rfc5424::StructuredData;
+use Severity;
+use MessageHeaderOverrides;
+use VecU8PushStr;
+
+
+/// Writes lines of the form `<3>message` to standard error, as understood by systemd's journald (see `sd-daemon(3)`)
+/// Each line of a multi-line message is written with its own prefix, so that journald assigns the same priority to all of them
+/// Structured data is not representable in this format and is ignored
+#[derive(Debug)]
+pub struct SdDaemonSyslogSender
+{
+	includeFacility: bool,
+	metrics: SyslogSenderMetrics,
+}
+
+impl SdDaemonSyslogSender
+{
+	/// If `includeFacility` is true, then the prefix is the full priority (eg `<134>`) rather than just the severity (eg `<6>`)
+	pub fn new(includeFacility: bool) -> SdDaemonSyslogSender
+	{
+		SdDaemonSyslogSender
+		{
+			includeFacility: includeFacility,
+			metrics: SyslogSenderMetrics::new(),
+		}
+	}
+	
+	/// Returns `None` if standard error is not connected to the journal, ie this process is not running as a systemd service
+	pub fn newIfStandardErrorIsConnectedToJournal(includeFacility: bool) -> Option<SdDaemonSyslogSender>
+	{
+		if SdDaemonSyslogSender::isStandardErrorConnectedToJournal()
+		{
+			Some(SdDaemonSyslogSender::new(includeFacility))
+		}
+		else
+		{
+			None
+		}
+	}
+	
+	/// systemd sets `JOURNAL_STREAM` to `device:inode` of the stream it connects to standard output and standard error
+	/// It is checked against standard error, as the variable is inherited by child processes which may have redirected it
+	pub fn isStandardErrorConnectedToJournal() -> bool
+	{
+		let journalStream = match var_os("JOURNAL_STREAM")
+		{
+			None => return false,
+			Some(journalStream) => journalStream,
+		};
+		
+		let journalStream = match journalStream.to_str()
+		{
+			None => return false,
+			Some(journalStream) => journalStream.to_owned(),
+		};
+		
+		let mut fields = journalStream.splitn(2, ':');
+		let device = match fields.next().and_then(|device| device.parse::<u64>().ok())
+		{
+			None => return false,
+			Some(device) => device,
+		};
+		let inode = match fields.next().and_then(|inode| inode.parse::<u64>().ok())
+		{
+			None => return false,
+			Some(inode) => inode,
+		};
+		
+		let mut statistics: stat = unsafe { zeroed() };
+		if unsafe { fstat(STDERR_FILENO, &mut statistics) } != 0
+		{
+			return false;
+		}
+		
+		statistics.st_dev as u64 == device && statistics.st_ino as u64 == inode
+	}
+	
+	fn write(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, message: &str) -> Vec<u8>
+	{
+		let prefix = if self.includeFacility
+		{
+			severity.toPriorityRfc3164(rfc3164Facility).to_string()
+		}
+		else
+		{
+			format!("<{}>", severity.toWire())
+		};
+		
+		// A trailing newline ends the last line, rather than starting an empty one
+		let message = if message.ends_with('\n')
+		{
+			&message[.. message.len() - 1]
+		}
+		else
+		{
+			message
+		};
+		
+		let mut writer: Vec<u8> = Vec::with_capacity(message.len() + 8);
+		for line in message.split('\n')
+		{
+			let line = line.trim_end_matches('\r');
+			writer.push_str(&prefix);
+			writer.push_str(line);
+			writer.push(b'\n');
+		}
+		writer
+	}
+}
+
+impl SyslogSender for SdDaemonSyslogSender
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	#[allow(unused_variables)]
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		let data = self.write(rfc3164Facility, severity, message);
+		
+		let startedAt = Instant::now();
+		let standardError = stderr();
+		let result = standardError.lock().write_all(&data);
+		self.metrics.record(data.len(), startedAt, &result);
+		result
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		Some(self.metrics.snapshot())
+	}
+}
+
+#[test]
+fn prefixesEveryLine()
+{
+	let severityOnly = SdDaemonSyslogSender::new(false);
+	assert_eq!(String::from_utf8(severityOnly.write(Rfc3164Facility::local0, Severity::LOG_INFO, "first\r\nsecond\nthird")).unwrap(), "<6>first\n<6>second\n<6>third\n");
+	assert_eq!(String::from_utf8(severityOnly.write(Rfc3164Facility::local0, Severity::LOG_INFO, "first\nsecond\r\n")).unwrap(), "<6>first\n<6>second\n");
+	assert_eq!(String::from_utf8(severityOnly.write(Rfc3164Facility::local0, Severity::LOG_INFO, "first\n\n")).unwrap(), "<6>first\n<6>\n");
+	
+	// The facility is shifted left by 3, not added, so local0.info is 16 * 8 + 6
+	let withFacility = SdDaemonSyslogSender::new(true);
+	assert_eq!(String::from_utf8(withFacility.write(Rfc3164Facility::local0, Severity::LOG_INFO, "first\nsecond")).unwrap(), "<134>first\n<134>second\n");
+	assert_eq!(String::from_utf8(withFacility.write(Rfc3164Facility::user, Severity::LOG_ERR, "message")).unwrap(), "<11>message\n");
+	assert_eq!(String::from_utf8(withFacility.write(Rfc3164Facility::kern, Severity::LOG_EMERG, "message")).unwrap(), "<0>message\n");
+}
@@ -0,0 +1,95 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::io::Result;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use rfc5424::StructuredData;
+use Selector;
+use Severity;
+use MessageHeaderOverrides;
+
+
+/// Wraps a `SyslogSender` so that only messages matched by a syslog.conf-style `Selector` are sent; all others are dropped
+#[derive(Debug)]
+pub struct SelectingSyslogSender<S: SyslogSender>
+{
+	sender: S,
+	selector: Selector,
+	dropped: AtomicUsize,
+}
+
+impl <S: SyslogSender> SelectingSyslogSender<S>
+{
+	pub fn new(sender: S, selector: Selector) -> SelectingSyslogSender<S>
+	{
+		SelectingSyslogSender
+		{
+			sender: sender,
+			selector: selector,
+			dropped: AtomicUsize::new(0),
+		}
+	}
+	
+	#[inline(always)]
+	pub fn selector(&self) -> &Selector
+	{
+		&self.selector
+	}
+	
+	/// Total number of messages dropped since creation
+	pub fn dropped(&self) -> usize
+	{
+		self.dropped.load(Ordering::Relaxed)
+	}
+}
+
+impl <S: SyslogSender> SyslogSender for SelectingSyslogSender<S>
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		if !self.selector.matches(rfc3164Facility, severity)
+		{
+			self.dropped.fetch_add(1, Ordering::Relaxed);
+			return Ok(());
+		}
+		
+		self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message)
+	}
+	
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		Some(self.sender.metrics().unwrap_or_default().withAdditionalDrops(self.dropped()))
+	}
+}
+
+#[test]
+fn sendsOnlySelectedMessages()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let selectingSyslogSender = SelectingSyslogSender::new(CapturingSyslogSender::forTesting(), "mail.warning;*.err".parse().unwrap());
+	let noStructuredData = StructuredData::new();
+	
+	selectingSyslogSender.send(Rfc3164Facility::mail, Severity::LOG_WARNING, &noStructuredData, "mail warning").unwrap();
+	selectingSyslogSender.send(Rfc3164Facility::mail, Severity::LOG_NOTICE, &noStructuredData, "mail notice").unwrap();
+	selectingSyslogSender.send(Rfc3164Facility::user, Severity::LOG_ERR, &noStructuredData, "user error").unwrap();
+	selectingSyslogSender.sendWithOverrides(Rfc3164Facility::user, Severity::LOG_WARNING, &MessageHeaderOverrides::messageId("noisy"), &noStructuredData, "user warning").unwrap();
+	
+	let sent: Vec<String> = selectingSyslogSender.sender.messages().into_iter().map(|capturedMessage| capturedMessage.message).collect();
+	assert_eq!(sent, vec!["mail warning", "user error"]);
+	assert_eq!(selectingSyslogSender.dropped(), 2);
+	
+	let metrics = selectingSyslogSender.metrics().unwrap();
+	assert_eq!(metrics.drops, 2);
+	assert_eq!(metrics.messagesSent, 2);
+}
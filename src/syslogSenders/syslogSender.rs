@@ -2,13 +2,31 @@
 // Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
 
 
-use syslog2Senders::Rfc3164Facility;
+use syslogSenders::Rfc3164Facility;
 use Severity;
 use std::io::Result;
 use rfc5424::StructuredData;
+use MessageHeaderOverrides;
+use syslogSenders::SyslogSenderMetricsSnapshot;
 
 
 pub trait SyslogSender
 {
 	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>;
+	
+	/// As `send()`, but replacing header fields such as MSGID for just this message
+	/// Senders which do not implement this ignore `messageHeaderOverrides`
+	#[allow(unused_variables)]
+	#[inline(always)]
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.send(rfc3164Facility, severity, structured_data_elements, message)
+	}
+	
+	/// Senders which do not keep metrics return None; wrap them in a `MeasuringSyslogSender` to do so
+	#[inline(always)]
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		None
+	}
 }
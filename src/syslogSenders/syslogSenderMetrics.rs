@@ -0,0 +1,143 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::collections::HashMap;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::io::Result;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+use std::time::Instant;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use syslogSenders::SendLatencyHistogramUpperBoundsInMicroseconds;
+
+
+/// Delivery counters for a sender; all updates are relaxed atomics (or a briefly held lock for errors), so are cheap enough to record on every send
+#[derive(Debug, Default)]
+pub struct SyslogSenderMetrics
+{
+	messagesSent: AtomicUsize,
+	bytesSent: AtomicUsize,
+	sendErrors: Mutex<HashMap<ErrorKind, usize>>,
+	retries: AtomicUsize,
+	truncations: AtomicUsize,
+	drops: AtomicUsize,
+	sendLatencyHistogram: [AtomicUsize; 7],
+}
+
+impl SyslogSenderMetrics
+{
+	pub fn new() -> SyslogSenderMetrics
+	{
+		SyslogSenderMetrics::default()
+	}
+	
+	/// Records the outcome of sending `bytes`, timed from `startedAt`
+	pub fn record(&self, bytes: usize, startedAt: Instant, result: &Result<()>)
+	{
+		self.recordLatency(startedAt.elapsed());
+		match *result
+		{
+			Ok(()) => self.recordSent(bytes),
+			Err(ref error) => self.recordError(error),
+		}
+	}
+	
+	#[inline(always)]
+	pub fn recordSent(&self, bytes: usize)
+	{
+		self.messagesSent.fetch_add(1, Ordering::Relaxed);
+		self.bytesSent.fetch_add(bytes, Ordering::Relaxed);
+	}
+	
+	pub fn recordError(&self, error: &Error)
+	{
+		let mut sendErrors = self.sendErrors.lock().unwrap();
+		*sendErrors.entry(error.kind()).or_insert(0) += 1;
+	}
+	
+	#[inline(always)]
+	pub fn recordRetry(&self)
+	{
+		self.retries.fetch_add(1, Ordering::Relaxed);
+	}
+	
+	#[inline(always)]
+	pub fn recordTruncation(&self)
+	{
+		self.truncations.fetch_add(1, Ordering::Relaxed);
+	}
+	
+	#[inline(always)]
+	pub fn recordDrop(&self)
+	{
+		self.drops.fetch_add(1, Ordering::Relaxed);
+	}
+	
+	pub fn recordLatency(&self, latency: Duration)
+	{
+		let microseconds = latency.as_secs().saturating_mul(1_000_000).saturating_add((latency.subsec_nanos() / 1_000) as u64);
+		let bucket = SendLatencyHistogramUpperBoundsInMicroseconds.iter().position(|&upperBound| microseconds < upperBound).unwrap_or(SendLatencyHistogramUpperBoundsInMicroseconds.len());
+		self.sendLatencyHistogram[bucket].fetch_add(1, Ordering::Relaxed);
+	}
+	
+	pub fn drops(&self) -> usize
+	{
+		self.drops.load(Ordering::Relaxed)
+	}
+	
+	pub fn snapshot(&self) -> SyslogSenderMetricsSnapshot
+	{
+		let mut sendLatencyHistogram = [0; 7];
+		for (index, count) in self.sendLatencyHistogram.iter().enumerate()
+		{
+			sendLatencyHistogram[index] = count.load(Ordering::Relaxed);
+		}
+		
+		SyslogSenderMetricsSnapshot
+		{
+			messagesSent: self.messagesSent.load(Ordering::Relaxed),
+			bytesSent: self.bytesSent.load(Ordering::Relaxed),
+			sendErrors: self.sendErrors.lock().unwrap().clone(),
+			retries: self.retries.load(Ordering::Relaxed),
+			truncations: self.truncations.load(Ordering::Relaxed),
+			drops: self.drops(),
+			sendLatencyHistogram: sendLatencyHistogram,
+		}
+	}
+}
+
+#[test]
+fn recordsOutcomesAndLatencies()
+{
+	let metrics = SyslogSenderMetrics::new();
+	let startedAt = Instant::now();
+	metrics.record(100, startedAt, &Ok(()));
+	metrics.record(50, startedAt, &Ok(()));
+	metrics.record(75, startedAt, &Err(Error::new(ErrorKind::TimedOut, "timed out")));
+	metrics.record(75, startedAt, &Err(Error::new(ErrorKind::TimedOut, "timed out")));
+	metrics.record(75, startedAt, &Err(Error::new(ErrorKind::ConnectionRefused, "refused")));
+	metrics.recordRetry();
+	metrics.recordTruncation();
+	metrics.recordDrop();
+	
+	metrics.recordLatency(Duration::from_millis(5));
+	metrics.recordLatency(Duration::from_secs(2));
+	
+	let snapshot = metrics.snapshot();
+	assert_eq!(snapshot.messagesSent, 2);
+	assert_eq!(snapshot.bytesSent, 150);
+	assert_eq!(snapshot.sendErrors.get(&ErrorKind::TimedOut), Some(&2));
+	assert_eq!(snapshot.sendErrors.get(&ErrorKind::ConnectionRefused), Some(&1));
+	assert_eq!(snapshot.sendErrorsTotal(), 3);
+	assert_eq!(snapshot.retries, 1);
+	assert_eq!(snapshot.truncations, 1);
+	assert_eq!(snapshot.drops, 1);
+	assert_eq!(snapshot.sendLatencyHistogram.iter().sum::<usize>(), 7);
+	assert_eq!(snapshot.sendLatencyHistogram[3], 1);
+	assert_eq!(snapshot.sendLatencyHistogram[6], 1);
+	
+	assert_eq!(snapshot.withAdditionalDrops(4).drops, 5);
+}
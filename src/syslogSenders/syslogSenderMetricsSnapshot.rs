@@ -0,0 +1,39 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+
+
+/// Upper bounds, in microseconds, of all but the last bucket of `sendLatencyHistogram`; the last bucket is unbounded
+pub const SendLatencyHistogramUpperBoundsInMicroseconds: [u64; 6] = [10, 100, 1_000, 10_000, 100_000, 1_000_000];
+
+/// A point-in-time copy of a sender's `SyslogSenderMetrics`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyslogSenderMetricsSnapshot
+{
+	pub messagesSent: usize,
+	pub bytesSent: usize,
+	pub sendErrors: HashMap<ErrorKind, usize>,
+	pub retries: usize,
+	pub truncations: usize,
+	/// Messages deliberately not sent, eg by rate-limiting or sampling
+	pub drops: usize,
+	/// Counts of sends (successful or not) by latency; see `SendLatencyHistogramUpperBoundsInMicroseconds`
+	pub sendLatencyHistogram: [usize; 7],
+}
+
+impl SyslogSenderMetricsSnapshot
+{
+	pub fn sendErrorsTotal(&self) -> usize
+	{
+		self.sendErrors.values().fold(0, |total, count| total + count)
+	}
+	
+	/// Used by wrapping senders to add the messages they dropped to those of the sender they wrap
+	pub fn withAdditionalDrops(mut self, drops: usize) -> SyslogSenderMetricsSnapshot
+	{
+		self.drops += drops;
+		self
+	}
+}
@@ -0,0 +1,120 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::io::Result;
+use std::sync::RwLock;
+use std::time::Duration;
+use std::time::Instant;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use syslogSenders::SyslogSenderMetricsSnapshot;
+use rfc5424::StructuredData;
+use rfc5424::TimeQuality;
+use Severity;
+use MessageHeaderOverrides;
+
+
+/// Wraps a `SyslogSender` so that every message has a `timeQuality` element taken from the kernel clock state
+/// Messages which already have a `timeQuality` element are passed through unchanged
+#[derive(Debug)]
+pub struct TimeQualitySyslogSender<S: SyslogSender>
+{
+	sender: S,
+	refreshInterval: Option<Duration>,
+	timeQuality: RwLock<(TimeQuality, Instant)>,
+}
+
+impl <S: SyslogSender> TimeQualitySyslogSender<S>
+{
+	/// If `refreshInterval` is None, the kernel is queried for every message; otherwise, the clock state is re-used until `refreshInterval` has elapsed
+	pub fn new(sender: S, refreshInterval: Option<Duration>) -> TimeQualitySyslogSender<S>
+	{
+		TimeQualitySyslogSender
+		{
+			sender: sender,
+			refreshInterval: refreshInterval,
+			timeQuality: RwLock::new((TimeQuality::current(), Instant::now())),
+		}
+	}
+	
+	/// Queries the kernel immediately
+	pub fn refresh(&self)
+	{
+		*self.timeQuality.write().unwrap() = (TimeQuality::current(), Instant::now());
+	}
+	
+	fn timeQuality(&self) -> TimeQuality
+	{
+		match self.refreshInterval
+		{
+			None => TimeQuality::current(),
+			Some(refreshInterval) =>
+			{
+				{
+					let timeQuality = self.timeQuality.read().unwrap();
+					if timeQuality.1.elapsed() < refreshInterval
+					{
+						return timeQuality.0;
+					}
+				}
+				self.refresh();
+				self.timeQuality.read().unwrap().0
+			}
+		}
+	}
+}
+
+impl <S: SyslogSender> SyslogSender for TimeQualitySyslogSender<S>
+{
+	#[inline(always)]
+	fn send(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		self.sendWithOverrides(rfc3164Facility, severity, &MessageHeaderOverrides::default(), structured_data_elements, message)
+	}
+	
+	fn sendWithOverrides(&self, rfc3164Facility: Rfc3164Facility, severity: Severity, messageHeaderOverrides: &MessageHeaderOverrides, structured_data_elements: &StructuredData, message: &str) -> Result<()>
+	{
+		if structured_data_elements.contains("timeQuality")
+		{
+			return self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, structured_data_elements, message);
+		}
+		
+		let mut annotated = structured_data_elements.clone();
+		try!(annotated.insert(self.timeQuality().element()));
+		self.sender.sendWithOverrides(rfc3164Facility, severity, messageHeaderOverrides, &annotated, message)
+	}
+	
+	#[inline(always)]
+	fn metrics(&self) -> Option<SyslogSenderMetricsSnapshot>
+	{
+		self.sender.metrics()
+	}
+}
+
+#[test]
+fn addsTheElementOnce()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let sender = TimeQualitySyslogSender::new(TimeQualitySyslogSender::new(CapturingSyslogSender::forTesting(), None), None);
+	sender.send(Rfc3164Facility::user, Severity::LOG_INFO, &StructuredData::new(), "hello").unwrap();
+	
+	let capturedMessages = sender.sender.sender.messages();
+	assert_eq!(capturedMessages[0].structuredData.iter().filter(|&&(ref id, _)| id == "timeQuality").count(), 1);
+	let isSynced = capturedMessages[0].structuredDataParameter("timeQuality", "isSynced").unwrap();
+	assert!(isSynced == "1" || capturedMessages[0].structuredDataParameter("timeQuality", "syncAccuracy").is_none());
+}
+
+#[test]
+fn leavesAnExistingElementAlone()
+{
+	use syslogSenders::CapturingSyslogSender;
+	
+	let sender = TimeQualitySyslogSender::new(CapturingSyslogSender::forTesting(), None);
+	let mut structuredData = StructuredData::new();
+	structuredData.insert(TimeQuality { tzKnown: false, isSynced: false, syncAccuracy: None }.element()).unwrap();
+	sender.send(Rfc3164Facility::user, Severity::LOG_INFO, &structuredData, "hello").unwrap();
+	
+	let capturedMessages = sender.sender.messages();
+	assert_eq!(capturedMessages[0].structuredData, vec![("timeQuality".to_owned(), vec![("tzKnown".to_owned(), "0".to_owned()), ("isSynced".to_owned(), "0".to_owned())])]);
+}
@@ -0,0 +1,56 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate tracing;
+use std::fmt::Debug;
+use self::tracing::field::Field;
+use self::tracing::field::Visit;
+
+
+/// Collects the fields of an event or span as strings
+#[derive(Debug, Default)]
+pub struct FieldVisitor
+{
+	extractMessage: bool,
+	pub message: Option<String>,
+	pub fields: Vec<(String, String)>,
+}
+
+impl FieldVisitor
+{
+	/// If `extractMessage` is true, the field called `message` (as created by `tracing`'s macros for the format string) is kept separately from the others
+	pub fn new(extractMessage: bool) -> FieldVisitor
+	{
+		FieldVisitor
+		{
+			extractMessage: extractMessage,
+			message: None,
+			fields: Vec::new(),
+		}
+	}
+	
+	fn record(&mut self, field: &Field, value: String)
+	{
+		if self.extractMessage && field.name() == "message"
+		{
+			self.message = Some(value);
+		}
+		else
+		{
+			self.fields.push((field.name().to_owned(), value));
+		}
+	}
+}
+
+impl Visit for FieldVisitor
+{
+	fn record_str(&mut self, field: &Field, value: &str)
+	{
+		self.record(field, value.to_owned());
+	}
+	
+	fn record_debug(&mut self, field: &Field, value: &dyn Debug)
+	{
+		self.record(field, format!("{:?}", value));
+	}
+}
@@ -0,0 +1,48 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+use std::borrow::Cow;
+use rfc5424::StructuredDataElement;
+use rfc5424::StructuredDataParameter;
+use rfc5424::TruncatedUsAsciiPrintableString;
+
+
+/// Stored in a span's extensions; names are converted to valid SD-NAMEs when recorded, so that events need only borrow them
+#[derive(Debug)]
+pub struct SpanFields
+{
+	sdId: TruncatedUsAsciiPrintableString,
+	fields: Vec<(TruncatedUsAsciiPrintableString, String)>,
+}
+
+impl SpanFields
+{
+	pub fn new(sdId: TruncatedUsAsciiPrintableString) -> SpanFields
+	{
+		SpanFields
+		{
+			sdId: sdId,
+			fields: Vec::new(),
+		}
+	}
+	
+	/// A field recorded again replaces its previous value, keeping its position
+	pub fn record(&mut self, fields: Vec<(String, String)>)
+	{
+		for (name, value) in fields
+		{
+			let name = TruncatedUsAsciiPrintableString::new_sd_name_replacing_invalid_characters(&name);
+			match self.fields.iter().position(|&(ref existingName, _)| *existingName == name)
+			{
+				None => self.fields.push((name, value)),
+				Some(index) => self.fields[index].1 = value,
+			}
+		}
+	}
+	
+	pub fn structuredDataElement<'a>(&'a self) -> StructuredDataElement<'a>
+	{
+		let parameters: Vec<StructuredDataParameter<'a>> = self.fields.iter().map(|&(ref name, ref value)| name.parameter(Cow::Borrowed(&value[..]))).collect();
+		StructuredDataElement::new(&self.sdId, parameters)
+	}
+}
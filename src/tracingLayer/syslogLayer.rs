@@ -0,0 +1,284 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+extern crate tracing;
+extern crate tracing_subscriber;
+use std::borrow::Cow;
+use self::tracing::Event;
+use self::tracing::Level;
+use self::tracing::Subscriber;
+use self::tracing::span::Attributes;
+use self::tracing::span::Id;
+use self::tracing::span::Record;
+use self::tracing_subscriber::layer::Context;
+use self::tracing_subscriber::layer::Layer;
+use self::tracing_subscriber::registry::LookupSpan;
+use tracingLayer::FieldVisitor;
+use tracingLayer::SpanFields;
+use syslogSenders::Rfc3164Facility;
+use syslogSenders::SyslogSender;
+use rfc5424::StructuredData;
+use rfc5424::StructuredDataElement;
+use rfc5424::StructuredDataParameter;
+use rfc5424::TruncatedUsAsciiPrintableString;
+use Severity;
+use MessageHeaderOverrides;
+
+
+/// A `tracing_subscriber::Layer` that sends events using any `SyslogSender`
+/// An event's fields become the parameters of a `fields@<PEN>` element, its target the MSGID and its `message` field the MSG
+/// The fields of the event's enclosing span become a `span.<span name>@<PEN>` element, so that a span can not share an SD-ID with the event's fields; the event's module path, file and line a `source@38188` element
+/// Field and span names are made into valid SD-NAMEs by replacing invalid characters with `_`
+#[derive(Debug)]
+pub struct SyslogLayer<S: SyslogSender>
+{
+	sender: S,
+	rfc3164Facility: Rfc3164Facility,
+	privateEnterpriseNumber: u32,
+	fieldsSdId: TruncatedUsAsciiPrintableString,
+	logSpanEnterAndExit: bool,
+}
+
+impl <S: SyslogSender> SyslogLayer<S>
+{
+	/// `privateEnterpriseNumber` is used to form the private SD-IDs of event fields and spans, eg StormMQ's is 38188
+	pub fn new(sender: S, rfc3164Facility: Rfc3164Facility, privateEnterpriseNumber: u32) -> SyslogLayer<S>
+	{
+		SyslogLayer
+		{
+			sender: sender,
+			rfc3164Facility: rfc3164Facility,
+			privateEnterpriseNumber: privateEnterpriseNumber,
+			fieldsSdId: TruncatedUsAsciiPrintableString::new_sd_name(&format!("fields@{}", privateEnterpriseNumber)),
+			logSpanEnterAndExit: false,
+		}
+	}
+	
+	/// If yes, entering and exiting a span is logged at `LOG_DEBUG`
+	pub fn logSpanEnterAndExit(&mut self, yes: bool)
+	{
+		self.logSpanEnterAndExit = yes;
+	}
+	
+	#[inline(always)]
+	fn severity(level: &Level) -> Severity
+	{
+		match *level
+		{
+			Level::ERROR => Severity::LOG_ERR,
+			Level::WARN => Severity::LOG_WARNING,
+			Level::INFO => Severity::LOG_INFO,
+			// Level::DEBUG and Level::TRACE; matching upon constants can not be exhaustive
+			_ => Severity::LOG_DEBUG,
+		}
+	}
+	
+	/// The name is shortened so that the whole SD-ID fits in 32 characters; `@` can not be used in the name part
+	/// The `span.` prefix keeps span SD-IDs distinct from `fields@<PEN>` and any other element an event has
+	fn spanSdId(&self, spanName: &str) -> TruncatedUsAsciiPrintableString
+	{
+		const Prefix: &'static str = "span.";
+		
+		let suffix = format!("@{}", self.privateEnterpriseNumber);
+		let mut name = String::with_capacity(32);
+		name.push_str(Prefix);
+		name.extend(spanName.chars().take(32 - Prefix.len() - suffix.len()).map(|character| if character != '@' && TruncatedUsAsciiPrintableString::is_valid_sd_name_character(character)
+		{
+			character
+		}
+		else
+		{
+			'_'
+		}));
+		
+		if name.len() == Prefix.len()
+		{
+			name.truncate(Prefix.len() - 1);
+		}
+		
+		TruncatedUsAsciiPrintableString::new_sd_name(&format!("{}{}", name, suffix))
+	}
+	
+	fn sendSpanTransition<Sub: Subscriber + for<'lookup> LookupSpan<'lookup>>(&self, transition: &str, id: &Id, context: Context<Sub>)
+	{
+		let span = match context.span(id)
+		{
+			None => return,
+			Some(span) => span,
+		};
+		
+		let extensions = span.extensions();
+		let mut structuredData = StructuredData::new();
+		if let Some(spanFields) = extensions.get::<SpanFields>()
+		{
+			// The only element, so never a duplicate
+			let _ = structuredData.insert(spanFields.structuredDataElement());
+		}
+		
+		let message = format!("{} {}", transition, span.name());
+		
+		// Layers have no way to report a failure
+		let _ = self.sender.sendWithOverrides(self.rfc3164Facility, Severity::LOG_DEBUG, &MessageHeaderOverrides::messageId(span.metadata().target()), &structuredData, &message);
+	}
+}
+
+impl <S: SyslogSender + Send + Sync + 'static, Sub: Subscriber + for<'lookup> LookupSpan<'lookup>> Layer<Sub> for SyslogLayer<S>
+{
+	fn on_new_span(&self, attributes: &Attributes, id: &Id, context: Context<Sub>)
+	{
+		let span = match context.span(id)
+		{
+			None => return,
+			Some(span) => span,
+		};
+		
+		let mut fieldVisitor = FieldVisitor::new(false);
+		attributes.record(&mut fieldVisitor);
+		
+		let mut spanFields = SpanFields::new(self.spanSdId(span.name()));
+		spanFields.record(fieldVisitor.fields);
+		span.extensions_mut().insert(spanFields);
+	}
+	
+	fn on_record(&self, id: &Id, values: &Record, context: Context<Sub>)
+	{
+		let span = match context.span(id)
+		{
+			None => return,
+			Some(span) => span,
+		};
+		
+		let mut fieldVisitor = FieldVisitor::new(false);
+		values.record(&mut fieldVisitor);
+		
+		if let Some(spanFields) = span.extensions_mut().get_mut::<SpanFields>()
+		{
+			spanFields.record(fieldVisitor.fields);
+		}
+	}
+	
+	fn on_event(&self, event: &Event, context: Context<Sub>)
+	{
+		let metadata = event.metadata();
+		
+		let mut fieldVisitor = FieldVisitor::new(true);
+		event.record(&mut fieldVisitor);
+		
+		// Declared before structuredData, which borrows from them
+		let span = context.event_span(event);
+		let extensions = span.as_ref().map(|span| span.extensions());
+		let parameterNames: Vec<TruncatedUsAsciiPrintableString> = fieldVisitor.fields.iter().map(|&(ref name, _)| TruncatedUsAsciiPrintableString::new_sd_name_replacing_invalid_characters(name)).collect();
+		
+		// The SD-IDs fields@PEN, span.<name>@PEN and source@38188 are distinct, so these are never duplicates
+		let mut structuredData = StructuredData::new();
+		
+		if !parameterNames.is_empty()
+		{
+			let parameters: Vec<StructuredDataParameter> = parameterNames.iter().zip(fieldVisitor.fields.iter()).map(|(name, &(_, ref value))| name.parameter(Cow::Borrowed(&value[..]))).collect();
+			let _ = structuredData.insert(StructuredDataElement::new(&self.fieldsSdId, parameters));
+		}
+		
+		if let Some(ref extensions) = extensions
+		{
+			if let Some(spanFields) = extensions.get::<SpanFields>()
+			{
+				let _ = structuredData.insert(spanFields.structuredDataElement());
+			}
+		}
+		
+		let _ = structuredData.insert(StructuredDataElement::source(metadata.module_path(), metadata.file(), metadata.line()));
+		
+		let message = match fieldVisitor.message
+		{
+			None => "",
+			Some(ref message) => &message[..],
+		};
+		
+		// Layers have no way to report a failure
+		let _ = self.sender.sendWithOverrides(self.rfc3164Facility, SyslogLayer::<S>::severity(metadata.level()), &MessageHeaderOverrides::messageId(metadata.target()), &structuredData, message);
+	}
+	
+	fn on_enter(&self, id: &Id, context: Context<Sub>)
+	{
+		if self.logSpanEnterAndExit
+		{
+			self.sendSpanTransition("enter", id, context);
+		}
+	}
+	
+	fn on_exit(&self, id: &Id, context: Context<Sub>)
+	{
+		if self.logSpanEnterAndExit
+		{
+			self.sendSpanTransition("exit", id, context);
+		}
+	}
+}
+
+#[test]
+fn spanNamedFieldsDoesNotCollideWithEventFields()
+{
+	use self::tracing::Dispatch;
+	use self::tracing::dispatcher::with_default;
+	use self::tracing_subscriber::layer::SubscriberExt;
+	use self::tracing_subscriber::registry::Registry;
+	use syslogSenders::CapturingSyslogSender;
+	
+	let dispatch = Dispatch::new(Registry::default().with(SyslogLayer::new(CapturingSyslogSender::forTesting(), Rfc3164Facility::local0, 32473)));
+	with_default(&dispatch, ||
+	{
+		let span = self::tracing::span!(Level::INFO, "fields", request = 7);
+		let _entered = span.enter();
+		self::tracing::event!(Level::WARN, user = "bob", "hello");
+		span.record("request", &8);
+		self::tracing::event!(Level::WARN, user = "bob", "again");
+	});
+	
+	let syslogLayer = dispatch.downcast_ref::<SyslogLayer<CapturingSyslogSender>>().unwrap();
+	assert_eq!(syslogLayer.spanSdId("fields").as_str(), "span.fields@32473");
+	assert_eq!(syslogLayer.spanSdId("").as_str(), "span@32473");
+	assert_eq!(syslogLayer.spanSdId("a@b c").as_str(), "span.a_b_c@32473");
+	assert_eq!(syslogLayer.spanSdId("a-span-name-which-is-far-too-long").as_str().len(), 32);
+	
+	let capturedMessages = syslogLayer.sender.messages();
+	assert_eq!(capturedMessages.len(), 2);
+	let capturedMessage = &capturedMessages[0];
+	assert_eq!(capturedMessage.message, "hello");
+	assert_eq!(capturedMessage.severity, Severity::LOG_WARNING);
+	assert_eq!(capturedMessage.structuredDataParameter("fields@32473", "user"), Some("bob"));
+	assert_eq!(capturedMessage.structuredDataParameter("span.fields@32473", "request"), Some("7"));
+	assert!(capturedMessage.hasStructuredDataElement("source@38188"));
+	
+	// Recording a field again replaces its value, rather than adding another
+	assert_eq!(capturedMessages[1].structuredDataParameters("span.fields@32473", "request"), vec!["8"]);
+}
+
+#[test]
+fn logsSpanEnterAndExit()
+{
+	use self::tracing::Dispatch;
+	use self::tracing::dispatcher::with_default;
+	use self::tracing_subscriber::layer::SubscriberExt;
+	use self::tracing_subscriber::registry::Registry;
+	use syslogSenders::CapturingSyslogSender;
+	
+	let mut syslogLayer = SyslogLayer::new(CapturingSyslogSender::forTesting(), Rfc3164Facility::local0, 32473);
+	syslogLayer.logSpanEnterAndExit(true);
+	let dispatch = Dispatch::new(Registry::default().with(syslogLayer));
+	with_default(&dispatch, ||
+	{
+		let span = self::tracing::span!(target: "requests", Level::INFO, "handle", request = 7);
+		let _entered = span.enter();
+	});
+	
+	let syslogLayer = dispatch.downcast_ref::<SyslogLayer<CapturingSyslogSender>>().unwrap();
+	let capturedMessages = syslogLayer.sender.messages();
+	let sent: Vec<&str> = capturedMessages.iter().map(|capturedMessage| &capturedMessage.message[..]).collect();
+	assert_eq!(sent, vec!["enter handle", "exit handle"]);
+	for capturedMessage in capturedMessages.iter()
+	{
+		assert_eq!(capturedMessage.severity, Severity::LOG_DEBUG);
+		assert_eq!(capturedMessage.messageId, Some("requests".to_owned()));
+		assert_eq!(capturedMessage.structuredDataParameter("span.handle@32473", "request"), Some("7"));
+	}
+}
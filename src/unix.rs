@@ -10,29 +10,28 @@ use self::libc::c_int;
 use self::libc::c_char;
 use std::ffi::CString;
 use std::ffi::CStr;
-use Priority;
+use LocalPriority;
 use Facility;
 use Severity;
 use LogOptions;
 use default_log_mask;
-use logMask::LogMaskTrait;
 
 static SyslogTemplate: &'static [u8] = b"%s\0";
 
 static UnformattableFatalPanic: &'static [u8] = b"Fatal panic (data unformattable)\0";
 
-pub fn syslog2_cstr(priority: Priority, message: &CStr)
+pub fn syslog2_cstr(priority: LocalPriority, message: &CStr)
 {
-	unsafe { self::libc::syslog2(priority as c_int, SyslogTemplate.as_ptr() as *const c_char, message); }
+	unsafe { self::libc::syslog(priority.value(), SyslogTemplate.as_ptr() as *const c_char, message.as_ptr()); }
 	
 	#[cfg(target_os = "solaris")]
 	log_to_standard_error_for_windows_and_solaris_cstr(priority, message);
 }
 
 // Exists because we need byte string constants, and these are for UNSIGNED bytes
-pub fn syslog2_bytes(priority: Priority, message: &[u8])
+pub fn syslog2_bytes(priority: LocalPriority, message: &[u8])
 {
-	unsafe { self::libc::syslog2(priority as c_int, SyslogTemplate.as_ptr() as *const c_char, message); }
+	unsafe { self::libc::syslog(priority.value(), SyslogTemplate.as_ptr() as *const c_char, message.as_ptr() as *const c_char); }
 	
 	#[cfg(target_os = "solaris")]
 	log_to_standard_error_for_windows_and_solaris_bytes(priority, message);
@@ -0,0 +1,40 @@
+// This file is part of syslog2. It is subject to the license terms in the COPYRIGHT file found in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT. No part of syslog2, including this file, may be copied, modified, propagated, or distributed except according to the terms contained in the COPYRIGHT file.
+// Copyright © 2016 The developers of syslog2. See the COPYRIGHT file in the top-level directory of this distribution and at https://raw.githubusercontent.com/lemonrock/syslog2/master/COPYRIGHT.
+
+#![cfg(feature = "derive")]
+#![allow(non_snake_case)]
+
+// An integration test, as the derived impl names `::syslog`, which is only this crate from outside it
+extern crate syslog;
+use syslog::rfc5424::StructuredDataElement;
+use syslog::rfc5424::ToStructuredDataElement;
+
+
+#[derive(StructuredDataElement)]
+#[sd(id = "http@38188")]
+#[allow(dead_code)]
+struct Http
+{
+	method: &'static str,
+	#[sd(rename = "status")] statusCode: u16,
+	#[sd(skip)] body: Vec<u8>,
+	userAgent: Option<String>,
+	referer: Option<String>,
+}
+
+#[test]
+fn derivedElementsRenderTheirFieldsInDeclarationOrder()
+{
+	let http = Http
+	{
+		method: "GET",
+		statusCode: 404,
+		body: vec![1, 2, 3],
+		userAgent: Some("curl/8 \"quoted\"".to_owned()),
+		referer: None,
+	};
+	
+	let mut rendered = Vec::new();
+	http.to_structured_data_element().write(&mut rendered);
+	assert_eq!(String::from_utf8(rendered).unwrap(), "[http@38188 method=\"GET\" status=\"404\" userAgent=\"curl/8 \\\"quoted\\\"\"]");
+}